@@ -106,7 +106,7 @@ fn main() -> Result<()> {
         "example::plugins::echo",
         "EchoPlugin",
         create_echo_plugin,
-    );
+    )?;
 
     registry.register_with_metadata(
         "reverse",
@@ -115,7 +115,7 @@ fn main() -> Result<()> {
         "example::plugins::reverse",
         "ReversePlugin",
         create_reverse_plugin,
-    );
+    )?;
 
     println!("Registered {} plugins", registry.count());
 
@@ -158,7 +158,7 @@ fn main() -> Result<()> {
             .map_err(|_| anyhow::anyhow!("Type mismatch"))?;
 
         let output = plugin.execute(test_input)?;
-        println!("  {} → \"{}\"", plugin.name(), output);
+        println!("  {} v{} → \"{}\"", plugin.name(), plugin.version(), output);
     }
 
     // Example 6: Check plugin existence
@@ -174,7 +174,7 @@ fn main() -> Result<()> {
     println!("------------------------");
 
     let global = ModuleRegistry::global();
-    global.register("global_echo", "plugin", create_echo_plugin);
+    global.register("global_echo", "plugin", create_echo_plugin)?;
 
     println!("Global registry has {} modules", global.count());
 
@@ -0,0 +1,129 @@
+//! Tamper-evident log of review decisions
+//!
+//! Each line of the audit file is a JSON `ReviewAuditRecord`. Every record stores the
+//! hash of the previous one, so editing or removing a line breaks the chain from that
+//! point on.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::registry::ModuleRegistry;
+use crate::types::CodeReviewStatus;
+
+/// Previous hash of the first record in an audit file
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One review decision in an audit file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewAuditRecord {
+    /// Module name
+    pub name: String,
+    /// Review status recorded for the module
+    pub status: CodeReviewStatus,
+    /// Time the record was appended (seconds since the Unix epoch)
+    pub timestamp: u64,
+    /// Hash of the previous record, or all zeros for the first one
+    pub prev_hash: String,
+    /// SHA-256 over this record's fields and `prev_hash`, hex-encoded
+    pub hash: String,
+}
+
+impl ReviewAuditRecord {
+    /// Compute the hash of the record's fields chained to `prev_hash`
+    fn compute_hash(&self) -> Result<String> {
+        let status = serde_json::to_string(&self.status)?;
+        let timestamp = self.timestamp.to_string();
+
+        let mut hasher = Sha256::new();
+        for field in [self.prev_hash.as_str(), &self.name, &status, &timestamp] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field.as_bytes());
+        }
+
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+}
+
+impl ModuleRegistry {
+    /// Append a review decision to the audit file at `path`, creating it if needed
+    ///
+    /// The record is timestamped with the registry clock and chained to the last line
+    /// of the file. Appends are not locked; writers sharing a file must serialize them.
+    pub fn append_review_audit(
+        &self,
+        path: &Path,
+        name: &str,
+        status: &CodeReviewStatus,
+    ) -> Result<()> {
+        let prev_hash = match fs::read_to_string(path) {
+            Ok(contents) => match contents.lines().rev().find(|line| !line.trim().is_empty()) {
+                Some(line) => {
+                    serde_json::from_str::<ReviewAuditRecord>(line)
+                        .with_context(|| {
+                            format!("Malformed last record in audit file: {}", path.display())
+                        })?
+                        .hash
+                }
+                None => GENESIS_HASH.to_string(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => GENESIS_HASH.to_string(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read audit file: {}", path.display()))
+            }
+        };
+
+        let mut record = ReviewAuditRecord {
+            name: name.to_string(),
+            status: status.clone(),
+            timestamp: self
+                .now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            prev_hash,
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash()?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit file: {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)
+            .with_context(|| format!("Failed to write audit file: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Re-validate the hash chain of an audit file
+    ///
+    /// Returns `Ok(false)` if any line is malformed, does not chain to the previous
+    /// line, or does not match its own hash. I/O errors are returned as errors.
+    pub fn verify_review_audit(path: &Path) -> Result<bool> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read audit file: {}", path.display()))?;
+
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let Ok(record) = serde_json::from_str::<ReviewAuditRecord>(line) else {
+                return Ok(false);
+            };
+            if record.prev_hash != prev_hash || record.compute_hash()? != record.hash {
+                return Ok(false);
+            }
+            prev_hash = record.hash;
+        }
+
+        Ok(true)
+    }
+}
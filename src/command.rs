@@ -0,0 +1,71 @@
+//! Command interface for driving a registry over IPC
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::registry::ModuleRegistry;
+use crate::security::SecurityCheckResult;
+use crate::types::{Module, ModuleDescription};
+
+/// Request handled by `ModuleRegistry::handle_command`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RegistryCommand {
+    /// List registered module names, sorted
+    List,
+    /// Describe one module
+    Describe { name: String },
+    /// Create one module and drop the instance
+    Create { name: String },
+    /// Run a security audit of every module
+    Audit,
+    /// Count registered modules
+    Count,
+}
+
+/// Response returned by `ModuleRegistry::handle_command`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RegistryResponse {
+    /// Registered module names
+    Modules(Vec<String>),
+    /// Description of one module
+    Description(Box<ModuleDescription>),
+    /// Module was created successfully
+    Created {
+        /// Registered module name
+        name: String,
+        /// Name reported by the instance, if it is a double-boxed `dyn Module`
+        module_name: Option<String>,
+    },
+    /// Security audit results per module
+    Audit(HashMap<String, SecurityCheckResult>),
+    /// Number of registered modules
+    Count(usize),
+    /// Command failed
+    Error(String),
+}
+
+impl ModuleRegistry {
+    /// Handle a command, mapping it to the matching registry method
+    ///
+    /// Failures are reported as `RegistryResponse::Error` rather than as a `Result`.
+    pub fn handle_command(&self, cmd: RegistryCommand) -> RegistryResponse {
+        match cmd {
+            RegistryCommand::List => RegistryResponse::Modules(self.list_modules_sorted()),
+            RegistryCommand::Describe { name } => match self.describe(&name) {
+                Some(description) => RegistryResponse::Description(Box::new(description)),
+                None => RegistryResponse::Error(format!("Module not found: {}", name)),
+            },
+            RegistryCommand::Create { name } => match self.create_any(&name) {
+                Ok(instance) => RegistryResponse::Created {
+                    module_name: instance
+                        .downcast_ref::<Box<dyn Module>>()
+                        .map(|module| module.name().to_string()),
+                    name,
+                },
+                Err(e) => RegistryResponse::Error(format!("{:#}", e)),
+            },
+            RegistryCommand::Audit => RegistryResponse::Audit(self.security_audit()),
+            RegistryCommand::Count => RegistryResponse::Count(self.count()),
+        }
+    }
+}
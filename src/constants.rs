@@ -5,6 +5,9 @@ pub const DEFAULT_MEMORY_LIMIT_MB: u64 = 128;
 pub const DEFAULT_CPU_LIMIT_PERCENT: u8 = 50;
 pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
 
+// WASM execution budget: fuel units (about one per instruction) per second at 100% CPU
+pub const WASM_FUEL_PER_CPU_SECOND: u64 = 100_000_000;
+
 // Security constants
 pub const SIGNATURE_EXPIRY_SECONDS: u64 = 365 * 24 * 60 * 60; // 1 year
 pub const DEFAULT_SIGNATURE_ALGORITHM: &str = "SHA256-RSA";
@@ -16,3 +19,10 @@ pub const DEFAULT_DENIED_PATHS: &[&str] = &["/etc", "/usr/bin", "/bin"];
 pub const MAX_MODULE_NAME_LENGTH: usize = 256;
 pub const MAX_MODULE_TYPE_LENGTH: usize = 128;
 pub const MAX_PATH_LENGTH: usize = 4096;
+pub const TRUNCATION_MARKER: &str = "...";
+pub const MAX_NOT_FOUND_CANDIDATES: usize = 5;
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+pub const DEFAULT_MAX_DEPENDENCY_DEPTH: usize = 32;
+
+// Event delivery
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
@@ -0,0 +1,124 @@
+//! Error types for module registry
+
+use thiserror::Error;
+
+/// Errors returned by registry operations
+///
+/// Registry methods return `anyhow::Result`; use `downcast_ref::<RegistryError>()`
+/// on the error to match on a specific cause.
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    /// Module name is empty, whitespace-only, or contains control characters
+    #[error("Invalid module name: {0:?}")]
+    InvalidName(String),
+
+    /// Module name exceeds `MAX_MODULE_NAME_LENGTH`
+    #[error("Module name too long: {length} bytes (max {max})")]
+    NameTooLong { length: usize, max: usize },
+
+    /// Module path exceeds `MAX_PATH_LENGTH`
+    #[error("Module path too long: {length} bytes (max {max})")]
+    PathTooLong { length: usize, max: usize },
+
+    /// Module type is empty, exceeds `MAX_MODULE_TYPE_LENGTH`, or fails the strict charset
+    #[error("Invalid module type: {0:?}")]
+    InvalidType(String),
+
+    /// Module type is not in the registry's allowlist
+    #[error("Module type not allowed: {0}")]
+    TypeNotAllowed(String),
+
+    /// Module failed the registry's security policy
+    #[error("Module failed security verification: {name} ({})", reasons.join("; "))]
+    SecurityCheckFailed { name: String, reasons: Vec<String> },
+
+    /// Module name is reserved but no factory has been bound with `fulfill` yet
+    #[error("Module reserved but not ready: {0}")]
+    NotReady(String),
+
+    /// Module is disabled
+    #[error("Module disabled: {0}")]
+    Disabled(String),
+
+    /// Singleton module requested through a path that returns an owned instance
+    #[error("Module is a singleton, use create_shared: {0}")]
+    Singleton(String),
+
+    /// Factory produced a value of a different type than registered with `register_typed`
+    #[error("Module factory produced an unexpected type: {0}")]
+    TypeAssertionFailed(String),
+
+    /// Module exceeded its creation rate limit
+    #[error("Module rate limited: {name}")]
+    RateLimited { name: String },
+
+    /// Module is not registered
+    ///
+    /// Reported among `TransactionAborted` failures; other lookups return a plain error.
+    #[error("Module not found: {0}")]
+    NotFound(String),
+
+    /// Transactional creation aborted because some modules are missing or failed verification
+    #[error(
+        "Transactional creation aborted: {}",
+        failures.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    TransactionAborted { failures: Vec<RegistryError> },
+
+    /// Module precondition failed, so the factory was not invoked
+    #[error("Module precondition failed: {name}: {error:#}")]
+    PreconditionFailed { name: String, error: anyhow::Error },
+
+    /// Module factory returned an error
+    ///
+    /// Only produced with the `backtrace` feature; otherwise the factory error is
+    /// returned with added context. The backtrace is boxed so `thiserror` does not
+    /// require nightly to provide it.
+    #[cfg(feature = "backtrace")]
+    #[error("Failed to instantiate module: {name}")]
+    FactoryFailed {
+        name: String,
+        source: anyhow::Error,
+        backtrace: Box<std::backtrace::Backtrace>,
+    },
+
+    /// Module instance was rejected by the module's instance validator
+    #[error("Invalid module instance: {name}: {error:#}")]
+    InvalidInstance { name: String, error: anyhow::Error },
+
+    /// Module is deprecated and the registry refuses to create deprecated modules
+    #[error("Module is deprecated: {name} ({note})")]
+    Deprecated { name: String, note: String },
+
+    /// Security metadata is sealed and can no longer be changed
+    #[error("Security metadata is sealed: {0}")]
+    SecuritySealed(String),
+
+    /// Module already has its maximum number of live guarded instances
+    #[error("Module has too many live instances: {name} (max {max})")]
+    TooManyInstances { name: String, max: usize },
+
+    /// Module is already registered and the conflict policy forbids replacing it
+    #[error("Module already registered: {0}")]
+    AlreadyRegistered(String),
+
+    /// Permission preset is neither registered nor built in
+    #[error("Unknown permission preset: {0}")]
+    UnknownPreset(String),
+
+    /// Module depends on a module that is not registered
+    #[error("Module {name} depends on unregistered module {dependency}")]
+    MissingDependency { name: String, dependency: String },
+
+    /// Module dependencies form a cycle through the given module
+    #[error("Dependency cycle through module: {0}")]
+    DependencyCycle(String),
+
+    /// Dependency chain is deeper than the registry's maximum
+    #[error("Dependency chain too deep: {depth} levels")]
+    DependencyTooDeep { depth: usize },
+
+    /// Module is ordered before or after its own type
+    #[error("Module {name} cannot be ordered relative to its own type {module_type}")]
+    OrderingContradiction { name: String, module_type: String },
+}
@@ -0,0 +1,139 @@
+//! Registry event subscription
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
+
+use crate::constants::EVENT_CHANNEL_CAPACITY;
+
+/// Event emitted when the registry changes
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryEvent {
+    /// A module was registered
+    Registered { name: String, module_type: String },
+    /// A registered module's metadata was updated
+    Updated { name: String },
+    /// All modules were removed
+    Cleared,
+    /// A module was disabled after repeated factory failures
+    CircuitOpened { name: String },
+    /// A module was moved to a new name
+    Renamed { old: String, new: String },
+}
+
+/// Bounded event queue shared between the registry and one subscriber
+struct Channel {
+    queue: Mutex<VecDeque<RegistryEvent>>,
+    ready: Condvar,
+    closed: Mutex<bool>,
+}
+
+/// Receiving end of a registry event subscription
+///
+/// Holds at most `EVENT_CHANNEL_CAPACITY` events; when a slow consumer falls
+/// behind, the oldest events are dropped.
+pub struct EventReceiver {
+    channel: Arc<Channel>,
+}
+
+impl EventReceiver {
+    /// Block until an event arrives, or return `None` once the registry is dropped
+    pub fn recv(&self) -> Option<RegistryEvent> {
+        let mut queue = self.channel.queue.lock().expect("Failed to acquire event lock");
+        loop {
+            if let Some(event) = queue.pop_front() {
+                return Some(event);
+            }
+            if *self.channel.closed.lock().expect("Failed to acquire event lock") {
+                return None;
+            }
+            queue = self
+                .channel
+                .ready
+                .wait(queue)
+                .expect("Failed to acquire event lock");
+        }
+    }
+
+    /// Wait up to `timeout` for an event
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RegistryEvent> {
+        let queue = self.channel.queue.lock().expect("Failed to acquire event lock");
+        let (mut queue, _) = self
+            .channel
+            .ready
+            .wait_timeout_while(queue, timeout, |queue| {
+                queue.is_empty()
+                    && !*self.channel.closed.lock().expect("Failed to acquire event lock")
+            })
+            .expect("Failed to acquire event lock");
+        queue.pop_front()
+    }
+
+    /// Take an event if one is queued
+    pub fn try_recv(&self) -> Option<RegistryEvent> {
+        self.channel
+            .queue
+            .lock()
+            .expect("Failed to acquire event lock")
+            .pop_front()
+    }
+}
+
+/// Fan-out of registry events to all live subscribers
+#[derive(Default)]
+pub(crate) struct EventBus {
+    subscribers: Mutex<Vec<Weak<Channel>>>,
+}
+
+impl EventBus {
+    /// Add a subscriber
+    pub(crate) fn subscribe(&self) -> EventReceiver {
+        let channel = Arc::new(Channel {
+            queue: Mutex::new(VecDeque::new()),
+            ready: Condvar::new(),
+            closed: Mutex::new(false),
+        });
+
+        self.subscribers
+            .lock()
+            .expect("Failed to acquire event lock")
+            .push(Arc::downgrade(&channel));
+
+        EventReceiver { channel }
+    }
+
+    /// Deliver an event to every live subscriber, dropping the oldest event when full
+    pub(crate) fn emit(&self, event: RegistryEvent) {
+        let mut subscribers = self.subscribers.lock().expect("Failed to acquire event lock");
+        subscribers.retain(|subscriber| {
+            let Some(channel) = subscriber.upgrade() else {
+                return false;
+            };
+
+            let mut queue = channel.queue.lock().expect("Failed to acquire event lock");
+            if queue.len() >= EVENT_CHANNEL_CAPACITY {
+                queue.pop_front();
+            }
+            queue.push_back(event.clone());
+            channel.ready.notify_all();
+            true
+        });
+    }
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        for subscriber in self
+            .subscribers
+            .get_mut()
+            .expect("Failed to acquire event lock")
+            .iter()
+        {
+            if let Some(channel) = subscriber.upgrade() {
+                *channel.closed.lock().expect("Failed to acquire event lock") = true;
+                let _queue = channel.queue.lock().expect("Failed to acquire event lock");
+                channel.ready.notify_all();
+            }
+        }
+    }
+}
@@ -12,7 +12,8 @@
 //!
 //! ## Quick Start
 //!
-//! ```rust
+#![cfg_attr(feature = "full", doc = "```rust")]
+#![cfg_attr(not(feature = "full"), doc = "```ignore")]
 //! use module_registry::{ModuleRegistry, Module};
 //! use anyhow::Result;
 //!
@@ -49,7 +50,7 @@
 //!     "uppercase",
 //!     "text_processor",
 //!     || Ok(Box::new(Box::new(UpperCaseModule) as Box<dyn TextProcessor>))
-//! );
+//! )?;
 //!
 //! // Create module instance  
 //! let any_module = registry.create_any("uppercase")?;
@@ -60,21 +61,87 @@
 //! # }
 //! ```
 
+#![cfg_attr(not(feature = "full"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "full")]
+pub mod audit;
+#[cfg(feature = "full")]
+pub mod command;
 pub mod constants;
+#[cfg(feature = "full")]
+pub mod error;
+#[cfg(feature = "full")]
+pub mod events;
 pub mod macros;
+#[cfg(feature = "core")]
+pub mod minimal;
+#[cfg(any(feature = "full", feature = "core"))]
+mod naming;
+#[cfg(feature = "full")]
 pub mod registry;
+#[cfg(feature = "full")]
 pub mod security;
+#[cfg(feature = "arc-swap")]
+mod swap;
+#[cfg(feature = "full")]
 pub mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export main types and functions
+#[cfg(feature = "full")]
+pub use audit::*;
+#[cfg(feature = "full")]
+pub use command::*;
 pub use constants::*;
-pub use macros::*;
+#[cfg(feature = "full")]
+pub use error::*;
+#[cfg(feature = "full")]
+pub use events::*;
+#[cfg(feature = "core")]
+pub use minimal::*;
+#[cfg(feature = "full")]
 pub use registry::*;
+#[cfg(feature = "full")]
 pub use security::*;
+#[cfg(feature = "full")]
 pub use types::*;
-
-// Re-export the main ModuleRegistry struct
-pub use registry::ModuleRegistry;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
 
 // Re-export inventory collection
-inventory::collect!(ModuleRegistration);
\ No newline at end of file
+#[cfg(feature = "inventory")]
+inventory::collect!(ModuleRegistration);
+
+// Compile-time check that the public types stay usable across threads
+#[cfg(any(feature = "full", feature = "core"))]
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[cfg(feature = "full")]
+const _: fn() = || {
+    assert_send_sync::<ModuleRegistry>();
+    assert_send_sync::<ModuleMetadata>();
+    assert_send_sync::<SecurityCheckResult>();
+    assert_send_sync::<RegistryError>();
+};
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_types_are_send_sync() {
+        assert_send_sync::<ModuleRegistry>();
+        assert_send_sync::<ModuleMetadata>();
+        assert_send_sync::<SecurityCheckResult>();
+        assert_send_sync::<RegistryError>();
+    }
+}
+
+#[cfg(feature = "core")]
+const _: fn() = || {
+    assert_send_sync::<CoreRegistry>();
+    assert_send_sync::<CoreError>();
+};
@@ -9,6 +9,7 @@
 ///
 /// register_module!("my_module", "MyModule", create_my_module);
 /// ```
+#[cfg(feature = "inventory")]
 #[macro_export]
 macro_rules! register_module {
     ($name:expr, $struct_name:expr, $factory:path) => {
@@ -0,0 +1,125 @@
+//! Minimal registry core for `no_std` hosts
+//!
+//! Needs only `alloc`: no locking, no security metadata and no `anyhow`.
+//! Enable with `default-features = false, features = ["core"]`.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt;
+
+use crate::constants::{MAX_MODULE_NAME_LENGTH, MAX_MODULE_TYPE_LENGTH};
+use crate::naming::trimmed_name;
+
+/// Errors returned by the minimal registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreError {
+    /// Module name is blank, contains control characters or exceeds `MAX_MODULE_NAME_LENGTH`
+    InvalidName(String),
+    /// Module type is empty or exceeds `MAX_MODULE_TYPE_LENGTH`
+    InvalidType(String),
+    /// No module is registered under the name
+    NotFound(String),
+    /// Factory failed to create the module
+    Factory(String),
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::InvalidName(name) => write!(f, "Invalid module name: {:?}", name),
+            CoreError::InvalidType(module_type) => {
+                write!(f, "Invalid module type: {:?}", module_type)
+            }
+            CoreError::NotFound(name) => write!(f, "Module not found: {}", name),
+            CoreError::Factory(message) => write!(f, "Failed to instantiate module: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl std::error::Error for CoreError {}
+
+/// Factory function type for the minimal registry
+pub type CoreFactory = fn() -> Result<Box<dyn Any + Send + Sync>, CoreError>;
+
+/// Registered module of the minimal registry
+struct CoreEntry {
+    module_type: String,
+    factory: CoreFactory,
+}
+
+/// Module registry for `no_std` + `alloc` builds
+///
+/// Registration takes `&mut self`; wrap the registry in your platform's lock to share it.
+#[derive(Default)]
+pub struct CoreRegistry {
+    modules: BTreeMap<String, CoreEntry>,
+}
+
+impl CoreRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a module with a factory function, replacing any module with the same name
+    ///
+    /// Names are trimmed and validated like `ModuleRegistry` names.
+    pub fn register(
+        &mut self,
+        name: &str,
+        module_type: &str,
+        factory: CoreFactory,
+    ) -> Result<(), CoreError> {
+        let name = trimmed_name(name)
+            .filter(|trimmed| trimmed.len() <= MAX_MODULE_NAME_LENGTH)
+            .ok_or_else(|| CoreError::InvalidName(name.to_string()))?;
+        if module_type.is_empty() || module_type.len() > MAX_MODULE_TYPE_LENGTH {
+            return Err(CoreError::InvalidType(module_type.to_string()));
+        }
+
+        self.modules.insert(
+            name.to_string(),
+            CoreEntry {
+                module_type: module_type.to_string(),
+                factory,
+            },
+        );
+        Ok(())
+    }
+
+    /// Create a module instance by name
+    pub fn create_any(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>, CoreError> {
+        let entry = self
+            .modules
+            .get(name)
+            .ok_or_else(|| CoreError::NotFound(name.to_string()))?;
+
+        (entry.factory)()
+    }
+
+    /// Get all registered module names, sorted
+    pub fn list_modules(&self) -> Vec<String> {
+        self.modules.keys().cloned().collect()
+    }
+
+    /// Check if a module is registered
+    pub fn has_module(&self, name: &str) -> bool {
+        self.modules.contains_key(name)
+    }
+
+    /// Get the type of a registered module
+    pub fn module_type(&self, name: &str) -> Option<&str> {
+        self.modules
+            .get(name)
+            .map(|entry| entry.module_type.as_str())
+    }
+
+    /// Get the number of registered modules
+    pub fn count(&self) -> usize {
+        self.modules.len()
+    }
+}
@@ -0,0 +1,13 @@
+//! Module name rules shared by `ModuleRegistry` and `CoreRegistry`
+
+/// Trim a module name, returning `None` if it is not a valid name
+///
+/// Valid names are non-empty after trimming surrounding whitespace and
+/// contain no control characters (such as newlines or tabs).
+pub(crate) fn trimmed_name(name: &str) -> Option<&str> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().any(char::is_control) {
+        return None;
+    }
+    Some(trimmed)
+}
@@ -1,45 +1,446 @@
 //! Module registry implementation
 
 use anyhow::{Context, Result};
-use std::any::Any;
-use std::collections::HashMap;
-use std::sync::{OnceLock, RwLock};
+use sha2::{Digest, Sha256};
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock, PoisonError, RwLock, Weak};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::security::{SecurityValidator, SecurityCheckResult};
+use crate::constants::*;
+use crate::error::RegistryError;
+use crate::events::{EventBus, EventReceiver, RegistryEvent};
+use crate::naming::trimmed_name;
+use crate::security::{SecurityCheckResult, SecurityPolicy, SecurityRiskLevel, SecurityValidator};
 use crate::types::*;
+#[cfg(feature = "wasm")]
+use crate::wasm::WasmModuleFactory;
 
 // Optional tracing support
 #[cfg(feature = "tracing")]
-use tracing::info;
+use crate::security::{SecurityIssue, SecuritySeverity};
+#[cfg(feature = "tracing")]
+use tracing::{debug, error, info, warn};
 
 #[cfg(not(feature = "tracing"))]
 macro_rules! info {
     ($($arg:tt)*) => {};
 }
 
+#[cfg(not(feature = "tracing"))]
+macro_rules! warn {
+    ($($arg:tt)*) => {};
+}
+
+/// Creation counters for a registered module
+#[derive(Default)]
+struct StatsCounters {
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    last_created: AtomicU64,
+    consecutive_failures: AtomicUsize,
+}
+
+impl StatsCounters {
+    fn record(&self, elapsed: Duration, timestamp: u64) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        self.last_created.store(timestamp, Ordering::Relaxed);
+    }
+
+    /// Record a factory failure, returning the number of failures in a row
+    fn record_failure(&self) -> usize {
+        self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.total_nanos.store(0, Ordering::Relaxed);
+        self.last_created.store(0, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> CreationStats {
+        let last_created = self.last_created.load(Ordering::Relaxed);
+        CreationStats {
+            creation_count: self.count.load(Ordering::Relaxed),
+            total_creation_time: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            last_created: (last_created > 0).then_some(last_created),
+        }
+    }
+}
+
+/// Token bucket enforcing a module's creation rate limit
+struct TokenBucket {
+    tokens: f64,
+    last_refill: SystemTime,
+}
+
+impl TokenBucket {
+    fn full(max: u32, now: SystemTime) -> Self {
+        Self {
+            tokens: f64::from(max),
+            last_refill: now,
+        }
+    }
+
+    /// Refill for the elapsed time and take a token if one is available
+    fn try_acquire(&mut self, max: u32, window: Duration, now: SystemTime) -> bool {
+        let elapsed = now.duration_since(self.last_refill).unwrap_or_default();
+        if !window.is_zero() {
+            let refill = elapsed.as_secs_f64() / window.as_secs_f64() * f64::from(max);
+            self.tokens = (self.tokens + refill).min(f64::from(max));
+        } else {
+            self.tokens = f64::from(max);
+        }
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Registry shared behind an `Arc`, created with `ModuleRegistry::into_shared`
+pub type SharedRegistry = Arc<ModuleRegistry>;
+
+/// Edge of the initialization graph walked by `dependency_order`
+#[derive(Clone, Copy)]
+enum Edge<'a> {
+    /// Named dependency that must be registered
+    Hard(&'a String),
+    /// Named dependency skipped when not registered
+    Soft(&'a String),
+    /// Registry key of a module ordered before this one by type
+    Key(&'a str),
+}
+
+/// Shared instance slot of a singleton or lazily initialized module
+type InstanceSlot = Arc<SlotCell>;
+
+/// Once-initialized instance cell whose lock is never held across a factory call
+#[derive(Default)]
+struct SlotCell {
+    state: Mutex<SlotState>,
+    ready: Condvar,
+}
+
+/// Initialization state of a `SlotCell`
+#[derive(Default)]
+enum SlotState {
+    #[default]
+    Empty,
+    /// The factory is running on this thread
+    Creating(ThreadId),
+    Ready(Arc<dyn Any + Send + Sync>),
+}
+
+impl SlotCell {
+    /// Lock the state, recovering from a poisoned lock
+    ///
+    /// The state is only replaced wholesale, so a panic never leaves it half-written.
+    fn lock(&self) -> MutexGuard<'_, SlotState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Return the instance if it has been created
+    fn get(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        match &*self.lock() {
+            SlotState::Ready(instance) => Some(instance.clone()),
+            _ => None,
+        }
+    }
+
+    /// Return the instance, running `init` without the lock held if the slot is empty
+    ///
+    /// Other threads wait for a running `init` and share its result. A call from the
+    /// thread already running `init` fails with `RegistryError::DependencyCycle`
+    /// instead of deadlocking. If `init` fails or panics, the slot is left empty and
+    /// the next call retries.
+    fn get_or_try_init(
+        &self,
+        name: &str,
+        init: impl FnOnce() -> Result<Arc<dyn Any + Send + Sync>>,
+    ) -> Result<Arc<dyn Any + Send + Sync>> {
+        let me = thread::current().id();
+        let mut state = self.lock();
+        loop {
+            match &*state {
+                SlotState::Ready(instance) => return Ok(instance.clone()),
+                SlotState::Creating(owner) if *owner == me => {
+                    return Err(RegistryError::DependencyCycle(name.to_string()).into());
+                }
+                SlotState::Creating(_) => {
+                    state = self
+                        .ready
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                SlotState::Empty => break,
+            }
+        }
+        *state = SlotState::Creating(me);
+        drop(state);
+
+        // Resets the slot if `init` fails or unwinds
+        struct Reset<'a>(&'a SlotCell);
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                *self.0.lock() = SlotState::Empty;
+                self.0.ready.notify_all();
+            }
+        }
+
+        let reset = Reset(self);
+        let created = init()?;
+        std::mem::forget(reset);
+        *self.lock() = SlotState::Ready(created.clone());
+        self.ready.notify_all();
+        Ok(created)
+    }
+}
+
+/// Lock guarding the module map
+///
+/// With the `arc-swap` feature, reads are wait-free snapshots and writes copy the map.
+#[cfg(not(feature = "arc-swap"))]
+type ModuleMap = RwLock<HashMap<String, ModuleEntry>>;
+#[cfg(feature = "arc-swap")]
+type ModuleMap = crate::swap::SwapLock<HashMap<String, ModuleEntry>>;
+
+/// Registered module with its factory and runtime state
+///
+/// Clones share runtime state (stats, rate bucket, instances) with the original.
+#[derive(Clone)]
+struct ModuleEntry {
+    metadata: ModuleMetadata,
+    factory: ModuleFactory,
+    stats: Arc<StatsCounters>,
+    schema: Option<SchemaProvider>,
+    precondition: Option<Precondition>,
+    context_factory: Option<ContextFactory>,
+    validator: Option<InstanceValidator>,
+    finalizer: Option<Finalizer>,
+    rate_bucket: Arc<Mutex<Option<TokenBucket>>>,
+    instance: InstanceSlot,
+    live: Arc<AtomicUsize>,
+    registration_index: u64,
+    reserved: bool,
+    #[cfg(feature = "wasm")]
+    wasm: Option<Arc<WasmModuleFactory>>,
+}
+
+/// Handles needed to run a module's factory outside the registry lock
+struct PendingCreate {
+    key: String,
+    factory: ModuleFactory,
+    stats: Arc<StatsCounters>,
+    singleton: bool,
+    instance: InstanceSlot,
+    expected_type_id: Option<TypeId>,
+    live: Arc<AtomicUsize>,
+    max_live: Option<usize>,
+    precondition: Option<Precondition>,
+    context_factory: Option<ContextFactory>,
+    validator: Option<InstanceValidator>,
+    rate_limit: Option<(u32, Duration)>,
+    rate_bucket: Arc<Mutex<Option<TokenBucket>>>,
+}
+
+/// Parts of a module entry set by specialised register paths, passed to `insert_entry`
+#[derive(Default)]
+struct EntryExtras {
+    schema: Option<SchemaProvider>,
+    precondition: Option<Precondition>,
+    context_factory: Option<ContextFactory>,
+    reserved: bool,
+    #[cfg(feature = "wasm")]
+    wasm: Option<Arc<WasmModuleFactory>>,
+    /// Metadata was supplied by the caller and keeps its timestamps and sandbox config
+    prebuilt: bool,
+    /// Check the metadata against the verify-on-register policy
+    verify: bool,
+}
+
+impl EntryExtras {
+    /// Extras for caller-supplied metadata, which is always verified
+    fn prebuilt() -> Self {
+        Self {
+            prebuilt: true,
+            verify: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl ModuleEntry {
+    fn new(metadata: ModuleMetadata, factory: ModuleFactory) -> Self {
+        static NEXT_REGISTRATION_INDEX: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            metadata,
+            factory,
+            stats: Arc::new(StatsCounters::default()),
+            schema: None,
+            precondition: None,
+            context_factory: None,
+            validator: None,
+            finalizer: None,
+            rate_bucket: Arc::new(Mutex::new(None)),
+            instance: InstanceSlot::default(),
+            live: Arc::new(AtomicUsize::new(0)),
+            registration_index: NEXT_REGISTRATION_INDEX.fetch_add(1, Ordering::Relaxed),
+            reserved: false,
+            #[cfg(feature = "wasm")]
+            wasm: None,
+        }
+    }
+
+    /// Describe the entry stored under `key`, listing the aliases that resolve to it
+    fn describe(&self, key: &str, aliases: &HashMap<String, String>) -> ModuleDescription {
+        let stats = self.stats.snapshot();
+        let mut module_aliases: Vec<String> = aliases
+            .iter()
+            .filter(|(_, target)| *target == key)
+            .map(|(alias, _)| alias.clone())
+            .collect();
+        module_aliases.sort_unstable();
+
+        ModuleDescription {
+            metadata: self.metadata.clone(),
+            creation_count: stats.creation_count,
+            last_created: stats.last_created,
+            enabled: self.metadata.enabled,
+            aliases: module_aliases,
+        }
+    }
+}
+
+impl PendingCreate {
+    /// Take a creation token if the module is rate limited
+    fn acquire_rate_token(&self, now: SystemTime) -> bool {
+        let Some((max, window)) = self.rate_limit else {
+            return true;
+        };
+
+        self.rate_bucket
+            .lock()
+            .expect("Failed to acquire rate limit lock")
+            .get_or_insert_with(|| TokenBucket::full(max, now))
+            .try_acquire(max, window, now)
+    }
+}
+
 /// Generic module registry
 ///
 /// Thread-safe registry for storing and instantiating modules at runtime.
 /// Modules are registered with a factory function and can be created by name.
 pub struct ModuleRegistry {
-    modules: RwLock<HashMap<String, (ModuleMetadata, ModuleFactory)>>,
+    modules: ModuleMap,
+    interceptor: RwLock<Option<FactoryInterceptor>>,
+    verify_policy: Option<SecurityPolicy>,
+    clock: RwLock<Arc<dyn Clock>>,
+    allowed_types: RwLock<Option<HashSet<String>>>,
+    decorator: RwLock<Option<MetadataDecorator>>,
+    normalizer: RwLock<Option<KeyNormalizer>>,
+    type_sandbox_defaults: RwLock<HashMap<String, SandboxConfig>>,
+    groups: RwLock<HashMap<String, Vec<String>>>,
+    presets: RwLock<HashMap<String, ModulePermissions>>,
+    aliases: RwLock<HashMap<String, String>>,
+    truncate_paths: AtomicBool,
+    strict_type_charset: AtomicBool,
+    security_sealed: AtomicBool,
+    deny_deprecated: AtomicBool,
+    max_dependency_depth: AtomicUsize,
+    failure_threshold: AtomicUsize,
+    events: EventBus,
+    handle: Weak<ModuleRegistry>,
 }
 
 impl ModuleRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
         Self {
-            modules: RwLock::new(HashMap::new()),
+            modules: ModuleMap::new(HashMap::new()),
+            interceptor: RwLock::new(None),
+            verify_policy: None,
+            clock: RwLock::new(Arc::new(SystemClock)),
+            allowed_types: RwLock::new(None),
+            decorator: RwLock::new(None),
+            normalizer: RwLock::new(None),
+            type_sandbox_defaults: RwLock::new(HashMap::new()),
+            groups: RwLock::new(HashMap::new()),
+            presets: RwLock::new(HashMap::new()),
+            aliases: RwLock::new(HashMap::new()),
+            truncate_paths: AtomicBool::new(false),
+            strict_type_charset: AtomicBool::new(false),
+            security_sealed: AtomicBool::new(false),
+            deny_deprecated: AtomicBool::new(false),
+            max_dependency_depth: AtomicUsize::new(DEFAULT_MAX_DEPENDENCY_DEPTH),
+            failure_threshold: AtomicUsize::new(0),
+            events: EventBus::default(),
+            handle: Weak::new(),
+        }
+    }
+
+    /// Move the registry behind an `Arc` so `registry_handle` can hand out weak references
+    pub fn into_shared(self) -> SharedRegistry {
+        Arc::new_cyclic(|handle| Self {
+            handle: handle.clone(),
+            ..self
+        })
+    }
+
+    /// Get a weak reference to this registry
+    ///
+    /// Modules can keep it and upgrade it later to look up sibling modules without
+    /// keeping the registry alive. Only registries created with `into_shared` can be
+    /// upgraded; for any other registry the handle is always empty.
+    pub fn registry_handle(&self) -> Weak<ModuleRegistry> {
+        self.handle.clone()
+    }
+
+    /// Start configuring a registry with a builder
+    pub fn builder() -> ModuleRegistryBuilder {
+        ModuleRegistryBuilder::default()
+    }
+
+    /// Create a registry that verifies secure registrations against a policy
+    ///
+    /// `register_secure` runs a comprehensive check at registration time and rejects
+    /// modules the policy does not allow. Plain `register` is not affected.
+    pub fn with_verify_on_register(policy: SecurityPolicy) -> Self {
+        Self {
+            verify_policy: Some(policy),
+            ..Self::new()
         }
     }
 
     /// Get the global registry instance
+    ///
+    /// Without the `inventory` feature the global registry starts empty.
     pub fn global() -> &'static Self {
         static REGISTRY: OnceLock<ModuleRegistry> = OnceLock::new();
         REGISTRY.get_or_init(|| {
             let registry = Self::new();
 
+            #[cfg(all(feature = "inventory", feature = "tracing", debug_assertions))]
+            for name in Self::validate_inventory() {
+                warn!("Duplicate inventory registration, last one wins: {}", name);
+            }
+
             // Load inventory-registered modules
+            #[cfg(feature = "inventory")]
             for reg in inventory::iter::<ModuleRegistration> {
                 let metadata = ModuleMetadata::new(
                     reg.name.to_string(),
@@ -52,7 +453,7 @@ impl ModuleRegistry {
                     .modules
                     .write()
                     .unwrap()
-                    .insert(metadata.name.clone(), (metadata, reg.factory));
+                    .insert(metadata.name.clone(), ModuleEntry::new(metadata, reg.factory));
             }
 
             info!(
@@ -64,10 +465,153 @@ impl ModuleRegistry {
         })
     }
 
+    /// Get a process-wide registry identified by `key`
+    ///
+    /// Each key gets its own registry, created empty on first use and independent of
+    /// `global()` and of every other key. Registries are never dropped.
+    pub fn named_global(key: &'static str) -> &'static Self {
+        static REGISTRIES: OnceLock<Mutex<HashMap<&'static str, &'static ModuleRegistry>>> =
+            OnceLock::new();
+        let mut registries = REGISTRIES
+            .get_or_init(Default::default)
+            .lock()
+            .expect("Failed to acquire named global lock");
+        registries
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(Self::new())))
+    }
+
+    /// Get names submitted more than once through `register_module!`
+    ///
+    /// `global()` keeps whichever duplicate inventory yields last, and logs a warning for
+    /// each duplicate in debug builds with `tracing`. Call this at startup to fail fast instead.
+    #[cfg(feature = "inventory")]
+    pub fn validate_inventory() -> Vec<String> {
+        duplicate_registration_names(inventory::iter::<ModuleRegistration>)
+    }
+
+    /// Load inventory-registered modules, resolving name conflicts with `policy`
+    ///
+    /// Registrations are inserted as-is, like in `global()`. With `ConflictPolicy::Error`,
+    /// nothing is loaded if any name is already registered.
+    #[cfg(feature = "inventory")]
+    pub fn load_inventory_with(&self, policy: ConflictPolicy) -> Result<LoadReport> {
+        // Keys and decorated metadata are built before taking the lock, since the
+        // normalizer and decorator are caller code.
+        let registrations: Vec<(String, &ModuleRegistration, ModuleMetadata)> =
+            inventory::iter::<ModuleRegistration>
+                .into_iter()
+                .map(|reg| {
+                    let mut metadata = ModuleMetadata::new(
+                        reg.name.to_string(),
+                        reg.module_type.to_string(),
+                        reg.instantiate_fn_name.to_string(),
+                        reg.module_path.to_string(),
+                        reg.struct_name.to_string(),
+                    );
+                    self.decorate(&mut metadata);
+                    (self.key(reg.name).into_owned(), reg, metadata)
+                })
+                .collect();
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+
+        if policy == ConflictPolicy::Error {
+            if let Some((_, reg, _)) = registrations
+                .iter()
+                .find(|(key, _, _)| modules.contains_key(key))
+            {
+                return Err(RegistryError::AlreadyRegistered(reg.name.to_string()).into());
+            }
+        }
+        if policy == ConflictPolicy::Overwrite {
+            if let Some((_, reg, _)) = registrations
+                .iter()
+                .find(|(key, _, _)| modules.contains_key(key))
+            {
+                self.check_unsealed(reg.name)?;
+            }
+        }
+
+        let mut report = LoadReport::default();
+        let mut loaded = Vec::new();
+        for (key, reg, metadata) in registrations {
+            if modules.contains_key(&key) {
+                if policy == ConflictPolicy::Skip {
+                    report.skipped += 1;
+                    continue;
+                }
+                report.overwritten += 1;
+            } else {
+                report.loaded += 1;
+            }
+
+            modules.insert(key, ModuleEntry::new(metadata, reg.factory));
+            loaded.push(reg);
+        }
+        drop(modules);
+
+        info!(
+            "Loaded inventory modules: {} new, {} skipped, {} overwritten",
+            report.loaded, report.skipped, report.overwritten
+        );
+        for reg in loaded {
+            self.events.emit(RegistryEvent::Registered {
+                name: reg.name.to_string(),
+                module_type: reg.module_type.to_string(),
+            });
+        }
+        Ok(report)
+    }
+
+    /// Register every `*.json` file in `dir` as a `ModuleMetadata`
+    ///
+    /// Modules are registered through `register_metadata` with a placeholder factory that
+    /// fails until a real one is bound with `replace_factory`. Files that cannot be read,
+    /// parsed or registered are listed in `LoadReport::failed` and do not stop the load.
+    /// Existing modules with the same name are overwritten.
+    pub fn load_metadata_dir(&self, dir: &Path) -> Result<LoadReport> {
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read metadata directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut report = LoadReport::default();
+        for path in paths {
+            let result = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| Ok(serde_json::from_str::<ModuleMetadata>(&contents)?))
+                .and_then(|metadata| {
+                    let existed = self.has_module(&metadata.name);
+                    self.register_metadata(metadata, unresolved_factory)?;
+                    Ok(existed)
+                });
+
+            match result {
+                Ok(true) => report.overwritten += 1,
+                Ok(false) => report.loaded += 1,
+                Err(e) => {
+                    warn!("Failed to load module metadata {}: {:#}", path.display(), e);
+                    report.failed.push((path, format!("{:#}", e)));
+                }
+            }
+        }
+
+        info!(
+            "Loaded metadata directory {}: {} new, {} overwritten, {} failed",
+            dir.display(),
+            report.loaded,
+            report.overwritten,
+            report.failed.len()
+        );
+        Ok(report)
+    }
+
     /// Register a module with a factory function
     ///
     /// The factory function should return a Box<dyn YourTrait> cast to Box<dyn Any + Send + Sync>
-    pub fn register(&self, name: &str, module_type: &str, factory: ModuleFactory) {
+    pub fn register(&self, name: &str, module_type: &str, factory: ModuleFactory) -> Result<()> {
         self.register_with_metadata(
             name,
             module_type,
@@ -75,195 +619,1887 @@ impl ModuleRegistry {
             module_path!(),
             "Module",
             factory,
-        );
+        )
     }
 
-    /// Register a module with full metadata
-    pub fn register_with_metadata(
-        &self,
-        name: &str,
-        module_type: &str,
-        instantiate_fn: &str,
-        module_path: &str,
-        struct_name: &str,
-        factory: ModuleFactory,
-    ) {
-        let metadata = ModuleMetadata::new(
-            name.to_string(),
-            module_type.to_string(),
-            instantiate_fn.to_string(),
-            module_path.to_string(),
-            struct_name.to_string(),
-        );
-
-        let mut modules = self.modules.write().expect("Failed to acquire write lock");
-        modules.insert(name.to_string(), (metadata, factory));
+    /// Restrict registration to the given module types
+    pub fn set_allowed_types<I, S>(&self, types: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        *self.allowed_types.write().expect("Failed to acquire write lock") =
+            Some(types.into_iter().map(Into::into).collect());
+    }
 
-        info!("Registered module: {} (type: {})", name, module_type);
+    /// Remove the module type allowlist
+    pub fn clear_allowed_types(&self) {
+        *self.allowed_types.write().expect("Failed to acquire write lock") = None;
     }
 
-    /// Create a module instance by name
+    /// Set a decorator applied to metadata just before insertion in every register path
     ///
-    /// Returns Box<dyn Any + Send + Sync> which you must downcast to your trait type
-    pub fn create_any(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-
-        let (_metadata, factory) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
-
-        info!("Creating module: {}", name);
-
-        factory().with_context(|| format!("Failed to instantiate module: {}", name))
+    /// The registry key is the registered name; changing `metadata.name` does not re-key the module.
+    pub fn set_metadata_decorator(&self, decorator: MetadataDecorator) {
+        *self.decorator.write().expect("Failed to acquire write lock") = Some(decorator);
     }
 
-    /// Create and downcast a module to a specific trait type
-    pub fn create<T: 'static>(&self, name: &str) -> Result<Box<T>> {
-        let any_module = self.create_any(name)?;
+    /// Remove the metadata decorator
+    pub fn clear_metadata_decorator(&self) {
+        *self.decorator.write().expect("Failed to acquire write lock") = None;
+    }
 
-        any_module
-            .downcast::<T>()
-            .map_err(|_| anyhow::anyhow!("Module type mismatch for: {}", name))
+    /// Set the sandbox configuration seeded into modules registered under `module_type`
+    ///
+    /// Applies to register paths that do not take a sandbox configuration, before the
+    /// metadata decorator runs. `register_metadata` keeps the configuration it is given.
+    pub fn set_type_sandbox_default(&self, module_type: &str, cfg: SandboxConfig) {
+        self.type_sandbox_defaults
+            .write()
+            .expect("Failed to acquire write lock")
+            .insert(module_type.to_string(), cfg);
     }
 
-    /// Get all registered module names
-    pub fn list_modules(&self) -> Vec<String> {
-        self.modules
+    /// Stamp the registry time, seed per-type defaults, then apply the metadata decorator
+    fn decorate(&self, metadata: &mut ModuleMetadata) {
+        metadata.touch_at(self.timestamp());
+        if let Some(cfg) = self
+            .type_sandbox_defaults
             .read()
             .expect("Failed to acquire read lock")
-            .keys()
-            .cloned()
-            .collect()
-    }
+            .get(&metadata.module_type)
+        {
+            metadata.sandbox_config = cfg.clone();
+        }
 
-    /// Get all registered module names (alias for compatibility)
-    pub fn get_module_names(&self) -> Vec<String> {
-        self.list_modules()
+        self.apply_decorator(metadata);
     }
 
-    /// Check if a module is registered
-    pub fn has_module(&self, name: &str) -> bool {
-        self.modules
+    /// Apply the metadata decorator, if one is set
+    fn apply_decorator(&self, metadata: &mut ModuleMetadata) {
+        let decorator = self
+            .decorator
             .read()
             .expect("Failed to acquire read lock")
-            .contains_key(name)
-    }
+            .clone();
 
-    /// Get metadata for a module
-    pub fn get_metadata(&self, name: &str) -> Option<ModuleMetadata> {
-        self.modules
-            .read()
-            .expect("Failed to acquire read lock")
-            .get(name)
-            .map(|(metadata, _)| metadata.clone())
+        if let Some(decorator) = decorator {
+            decorator(metadata);
+        }
     }
 
-    /// Clear all registered modules (for testing)
-    pub fn clear(&self) {
-        self.modules
-            .write()
-            .expect("Failed to acquire write lock")
-            .clear();
+    /// Set a function normalizing module names into registry keys
+    ///
+    /// Applied on insert and on every lookup; the default is identity. Metadata keeps the
+    /// name as registered. If two names normalize to the same key, the last registration wins.
+    /// Set the normalizer before registering modules, since existing keys are not re-normalized.
+    pub fn set_key_normalizer(&self, normalizer: KeyNormalizer) {
+        *self.normalizer.write().expect("Failed to acquire write lock") = Some(normalizer);
     }
 
-    /// Get count of registered modules
-    pub fn count(&self) -> usize {
-        self.modules
+    /// Normalize a module name into its registry key, following aliases left by `rename`
+    fn key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let key = self.normalized_key(name);
+        match self
+            .aliases
             .read()
             .expect("Failed to acquire read lock")
-            .len()
+            .get(key.as_ref())
+        {
+            Some(target) => Cow::Owned(target.clone()),
+            None => key,
+        }
     }
 
-    /// Verify module signature
-    pub fn verify_module_signature(&self, name: &str) -> Result<bool> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-        let (metadata, _) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
-
-        SecurityValidator::verify_signature(metadata)
+    /// Normalize a module name into its registry key, ignoring aliases
+    fn normalized_key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match &*self.normalizer.read().expect("Failed to acquire read lock") {
+            Some(normalizer) => Cow::Owned(normalizer(name)),
+            None => Cow::Borrowed(name),
+        }
     }
 
-    /// Check if module has required permissions
-    pub fn check_module_permissions(&self, name: &str, required_permission: &str) -> Result<bool> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-        let (metadata, _) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
-
-        SecurityValidator::check_permissions(metadata, required_permission)
+    /// Move a module to a new name, keeping its factory, metadata, stats and instances
+    ///
+    /// Fails if `old` is not registered or `new` is already taken. Groups and dependencies
+    /// naming `old` are not updated; use `rename_with_alias` to keep `old` resolving.
+    pub fn rename(&self, old: &str, new: &str) -> Result<()> {
+        self.rename_entry(old, new, false)
     }
 
-    /// Check if module passed code review
-    pub fn is_module_approved(&self, name: &str) -> Result<bool> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-        let (metadata, _) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
-
-        SecurityValidator::is_approved(metadata)
+    /// Move a module to a new name and keep `old` as an alias of it
+    ///
+    /// The alias behaves like the new name for every lookup, including registration,
+    /// until it is removed with `remove_alias`.
+    pub fn rename_with_alias(&self, old: &str, new: &str) -> Result<()> {
+        self.rename_entry(old, new, true)
     }
 
-    /// Verify supply chain information
-    pub fn verify_supply_chain(&self, name: &str) -> Result<bool> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-        let (metadata, _) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
-
-        SecurityValidator::verify_supply_chain(metadata)
+    /// Remove an alias left by `rename_with_alias`, returning whether it existed
+    pub fn remove_alias(&self, alias: &str) -> bool {
+        let key = self.normalized_key(alias);
+        self.aliases
+            .write()
+            .expect("Failed to acquire write lock")
+            .remove(key.as_ref())
+            .is_some()
     }
 
-    /// Create module with security checks
-    pub fn create_secure(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
-        // Verify signature
-        if !self.verify_module_signature(name)? {
-            return Err(anyhow::anyhow!("Module signature verification failed: {}", name));
-        }
-
-        // Check if module is approved
-        if !self.is_module_approved(name)? {
-            return Err(anyhow::anyhow!("Module not approved: {}", name));
-        }
+    /// Move a module's entry under one write lock, optionally aliasing the old key
+    fn rename_entry(&self, old: &str, new: &str, keep_alias: bool) -> Result<()> {
+        let old_key = self.key(old).into_owned();
 
-        // Verify supply chain
-        if !self.verify_supply_chain(name)? {
-            return Err(anyhow::anyhow!("Supply chain verification failed: {}", name));
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let module_type = modules
+            .get(&old_key)
+            .ok_or_else(|| module_not_found(old))?
+            .metadata
+            .module_type
+            .clone();
+        let new = self.check_registration(new, &module_type)?;
+        let new_key = self.normalized_key(new).into_owned();
+        if modules.contains_key(&new_key) {
+            return Err(RegistryError::AlreadyRegistered(new.to_string()).into());
         }
 
-        // Create module with sandboxing
-        self.create_with_sandbox(name)
-    }
-
-    /// Create module with sandbox configuration
-    pub fn create_with_sandbox(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
-        let modules = self.modules.read().expect("Failed to acquire read lock");
-        let (metadata, factory) = modules
-            .get(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
+        let mut entry = modules.remove(&old_key).expect("Module checked above");
+        entry.metadata.name = new.to_string();
+        entry.metadata.touch_at(self.timestamp());
+        modules.insert(new_key.clone(), entry);
 
-        // Apply sandbox configuration
-        if metadata.sandbox_config.enabled {
-            info!("Creating sandboxed module: {}", name);
-            // In a real implementation, set up sandbox environment
-            // For now, just log the sandbox config
-            info!("Sandbox config: {:?}", metadata.sandbox_config);
+        let mut aliases = self.aliases.write().expect("Failed to acquire write lock");
+        aliases.remove(&new_key);
+        for target in aliases.values_mut() {
+            if *target == old_key {
+                *target = new_key.clone();
+            }
         }
+        if keep_alias {
+            aliases.insert(old_key, new_key);
+        }
+        drop(aliases);
+        drop(modules);
 
-        info!("Creating module: {}", name);
-        factory().with_context(|| format!("Failed to instantiate module: {}", name))
+        info!("Renamed module: {} -> {}", old, new);
+        self.events.emit(RegistryEvent::Renamed {
+            old: old.to_string(),
+            new: new.to_string(),
+        });
+        Ok(())
     }
 
-    /// Register module with security metadata
-    pub fn register_secure(
+    /// Check whether a registration would be accepted, without inserting it
+    ///
+    /// Runs the same decorator and checks as `register_metadata` for `meta` registered
+    /// under `name` and `module_type`, and returns the first violation.
+    pub fn validate_registration(
         &self,
         name: &str,
         module_type: &str,
-        factory: ModuleFactory,
-        signature: Option<ModuleSignature>,
+        meta: &ModuleMetadata,
+    ) -> Result<(), RegistryError> {
+        let mut metadata = meta.clone();
+        metadata.name = name.to_string();
+        metadata.module_type = module_type.to_string();
+        let name = self.check_metadata(&mut metadata, &EntryExtras::prebuilt())?;
+        if self.has_module(&name) {
+            self.check_unsealed(&name)?;
+        }
+        Ok(())
+    }
+
+    /// Run every registration check on metadata about to be inserted, returning the trimmed name
+    ///
+    /// Validates the name and type, decorates the metadata, enforces the module path limit
+    /// and, if requested, the verify-on-register policy. Over-long module paths are
+    /// truncated or rejected according to `set_truncate_paths`. Pre-built metadata keeps
+    /// its timestamps and sandbox configuration.
+    fn check_metadata(
+        &self,
+        metadata: &mut ModuleMetadata,
+        extras: &EntryExtras,
+    ) -> Result<String, RegistryError> {
+        let name = self
+            .check_registration(&metadata.name, &metadata.module_type)?
+            .to_string();
+        metadata.name.clone_from(&name);
+
+        if extras.prebuilt {
+            self.apply_decorator(metadata);
+        } else {
+            self.decorate(metadata);
+        }
+
+        if let Cow::Owned(path) = self.check_module_path(&metadata.module_path)? {
+            metadata.module_path = path;
+        }
+
+        if extras.verify {
+            self.check_security(&name, metadata)?;
+        }
+        Ok(name)
+    }
+
+    /// Validate the name and type of a registration, returning the trimmed name
+    fn check_registration<'a>(
+        &self,
+        name: &'a str,
+        module_type: &str,
+    ) -> Result<&'a str, RegistryError> {
+        let name = validated_name(name)?;
+        if name.len() > MAX_MODULE_NAME_LENGTH {
+            return Err(RegistryError::NameTooLong {
+                length: name.len(),
+                max: MAX_MODULE_NAME_LENGTH,
+            });
+        }
+
+        if module_type.is_empty() || module_type.len() > MAX_MODULE_TYPE_LENGTH {
+            return Err(RegistryError::InvalidType(module_type.to_string()));
+        }
+
+        if self.strict_type_charset.load(Ordering::Relaxed)
+            && !module_type
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/'))
+        {
+            return Err(RegistryError::InvalidType(module_type.to_string()));
+        }
+
+        if let Some(allowed) = &*self.allowed_types.read().expect("Failed to acquire read lock") {
+            if !allowed.contains(module_type) {
+                return Err(RegistryError::TypeNotAllowed(module_type.to_string()));
+            }
+        }
+
+        Ok(name)
+    }
+
+    /// Restrict module types to ASCII identifiers matching `[a-zA-Z0-9_.\-/]+`
+    ///
+    /// Off by default, in which case any non-empty UTF-8 type is accepted.
+    pub fn set_strict_type_charset(&self, strict: bool) {
+        self.strict_type_charset.store(strict, Ordering::Relaxed);
+    }
+
+    /// Refuse to create deprecated modules instead of logging a warning
+    ///
+    /// When set, creation fails with `RegistryError::Deprecated`. Off by default.
+    pub fn set_deny_deprecated(&self, deny: bool) {
+        self.deny_deprecated.store(deny, Ordering::Relaxed);
+    }
+
+    /// Truncate over-long module paths instead of rejecting them
+    pub fn set_truncate_paths(&self, truncate: bool) {
+        self.truncate_paths.store(truncate, Ordering::Relaxed);
+    }
+
+    /// Enforce `MAX_PATH_LENGTH`, truncating with a marker if enabled
+    fn check_module_path<'a>(&self, module_path: &'a str) -> Result<Cow<'a, str>, RegistryError> {
+        if module_path.len() <= MAX_PATH_LENGTH {
+            return Ok(Cow::Borrowed(module_path));
+        }
+
+        if !self.truncate_paths.load(Ordering::Relaxed) {
+            return Err(RegistryError::PathTooLong {
+                length: module_path.len(),
+                max: MAX_PATH_LENGTH,
+            });
+        }
+
+        let budget = MAX_PATH_LENGTH - TRUNCATION_MARKER.len();
+        let end = module_path
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .take_while(|&end| end <= budget)
+            .last()
+            .unwrap_or(0);
+
+        Ok(Cow::Owned(format!("{}{}", &module_path[..end], TRUNCATION_MARKER)))
+    }
+
+    /// Check metadata against the verify-on-register policy, if one is set
+    fn check_security(&self, name: &str, metadata: &ModuleMetadata) -> Result<(), RegistryError> {
+        let Some(policy) = &self.verify_policy else {
+            return Ok(());
+        };
+
+        let check = SecurityValidator::comprehensive_check_with_policy(metadata, policy);
+        let blocking = policy.blocking_issues(&check);
+        if blocking.is_empty() {
+            return Ok(());
+        }
+
+        Err(RegistryError::SecurityCheckFailed {
+            name: name.to_string(),
+            reasons: blocking.iter().map(|i| i.message.clone()).collect(),
+        })
+    }
+
+    /// Register a module with full metadata
+    pub fn register_with_metadata(
+        &self,
+        name: &str,
+        module_type: &str,
+        instantiate_fn: &str,
+        module_path: &str,
+        struct_name: &str,
+        factory: ModuleFactory,
+    ) -> Result<()> {
+        let metadata = ModuleMetadata::new(
+            name.to_string(),
+            module_type.to_string(),
+            instantiate_fn.to_string(),
+            module_path.to_string(),
+            struct_name.to_string(),
+        );
+        self.insert_entry(metadata, factory, EntryExtras::default())
+    }
+
+    /// Register a module with pre-built metadata
+    ///
+    /// The metadata is stored as given, after the decorator and the same checks as
+    /// `validate_registration`. The module is keyed by the trimmed `meta.name`. Over-long
+    /// module paths are truncated when path truncation is enabled, like in every register path.
+    pub fn register_metadata(&self, meta: ModuleMetadata, factory: ModuleFactory) -> Result<()> {
+        self.insert_entry(meta, factory, EntryExtras::prebuilt())
+    }
+
+    /// Register a module whose factory receives a `RegistryContext`
+    ///
+    /// The factory can resolve its own dependencies through `ctx.registry()`. It runs for
+    /// every create path; `create_with_context` additionally passes host data.
+    pub fn register_with_context(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ContextFactory,
+    ) -> Result<()> {
+        let metadata = registry_metadata(name, module_type, "context_factory");
+        let extras = EntryExtras {
+            context_factory: Some(factory),
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, unresolved_factory, extras)
+    }
+
+    /// Register a sandboxed WASM module
+    ///
+    /// The binary is compiled once; every create instantiates it as a `WasmInstance`
+    /// with memory limited by the module's current permissions. Replacing the factory
+    /// detaches the WASM module.
+    #[cfg(feature = "wasm")]
+    pub fn register_wasm(&self, name: &str, module_type: &str, wasm_bytes: &[u8]) -> Result<()> {
+        let factory = WasmModuleFactory::new(wasm_bytes)
+            .with_context(|| format!("Failed to load WASM module: {}", name.trim()))?;
+        let mut metadata = registry_metadata(name, module_type, "wasm");
+        metadata.struct_name = "WasmInstance".to_string();
+        let extras = EntryExtras {
+            context_factory: Some(wasm_factory),
+            wasm: Some(Arc::new(factory)),
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, unresolved_factory, extras)
+    }
+
+    /// Reserve a module name before its factory is available
+    ///
+    /// The module is listed and described like any other, but creating it fails with
+    /// `RegistryError::NotReady` until a factory is bound with `fulfill`.
+    pub fn reserve(&self, name: &str, module_type: &str) -> Result<()> {
+        let metadata = registry_metadata(name, module_type, "reserved");
+        let extras = EntryExtras {
+            reserved: true,
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, unresolved_factory, extras)
+    }
+
+    /// Bind the factory of a module reserved with `reserve`
+    ///
+    /// Fails with `RegistryError::AlreadyRegistered` if the module is not reserved.
+    pub fn fulfill(&self, name: &str, factory: ModuleFactory) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let entry = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        if !entry.reserved {
+            return Err(RegistryError::AlreadyRegistered(name.to_string()).into());
+        }
+
+        entry.factory = factory;
+        entry.reserved = false;
+        entry.metadata.instantiate_fn_name = "factory".to_string();
+        entry.metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Fulfilled reserved module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Register a module together with a configuration schema provider
+    pub fn register_with_schema(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+        schema: SchemaProvider,
+    ) -> Result<()> {
+        let metadata = registry_metadata(name, module_type, "factory");
+        let extras = EntryExtras {
+            schema: Some(schema),
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, factory, extras)
+    }
+
+    /// Get the configuration schema of a module, if it provides one
+    pub fn config_schema(&self, name: &str) -> Option<serde_json::Value> {
+        let schema = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .and_then(|entry| entry.schema)?;
+
+        Some(schema())
+    }
+
+    /// Register a module only if no module with this name exists
+    ///
+    /// The check and insert happen under a single write lock. Returns whether the module was inserted.
+    pub fn get_or_register(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+    ) -> Result<bool> {
+        let mut metadata = registry_metadata(name, module_type, "factory");
+        let name = self.check_metadata(&mut metadata, &EntryExtras::default())?;
+
+        let key = self.key(&name).into_owned();
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        if modules.contains_key(&key) {
+            return Ok(false);
+        }
+
+        modules.insert(key, ModuleEntry::new(metadata, factory));
+        drop(modules);
+
+        info!("Registered module: {} (type: {})", name, module_type);
+        self.events.emit(RegistryEvent::Registered {
+            name,
+            module_type: module_type.to_string(),
+        });
+        Ok(true)
+    }
+
+    /// Register a module whose factory must produce a value of type `T`
+    ///
+    /// `T` is the type stored in the `Any`, e.g. `Box<dyn YourTrait>` for double-boxed factories.
+    /// Creation fails with `RegistryError::TypeAssertionFailed` if the factory produces anything else.
+    pub fn register_typed<T: 'static>(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+    ) -> Result<()> {
+        let mut metadata = registry_metadata(name, module_type, "factory");
+        metadata.expected_type_id = Some(TypeId::of::<T>());
+        self.insert_entry(metadata, factory, EntryExtras::default())
+    }
+
+    /// Register a module whose factory only runs once `precondition` passes
+    ///
+    /// Creation fails with `RegistryError::PreconditionFailed` while the precondition errors.
+    pub fn register_with_precondition(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+        precondition: Precondition,
+    ) -> Result<()> {
+        let metadata = registry_metadata(name, module_type, "factory");
+        let extras = EntryExtras {
+            precondition: Some(precondition),
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, factory, extras)
+    }
+
+    /// Register a singleton module
+    ///
+    /// Singletons have at most one live instance, shared through `create_shared`.
+    /// Calling `create_any` on a singleton fails with `RegistryError::Singleton`.
+    pub fn register_singleton(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+    ) -> Result<()> {
+        let mut metadata = registry_metadata(name, module_type, "factory");
+        metadata.singleton = true;
+        self.insert_entry(metadata, factory, EntryExtras::default())
+    }
+
+    /// Create a module instance by name
+    ///
+    /// Returns Box<dyn Any + Send + Sync> which you must downcast to your trait type
+    pub fn create_any(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
+        let pending = self.prepare_create(name, false)?;
+
+        info!("Creating module: {}", name);
+
+        self.invoke_factory(name, &pending)
+    }
+
+    /// Create a module instance, passing host data to its context factory
+    ///
+    /// `host` is available to the factory through `RegistryContext::host`, e.g. a scratch
+    /// allocator or logger. Modules with a plain factory ignore it. `create_any` calls
+    /// context factories without host data.
+    pub fn create_with_context(
+        &self,
+        name: &str,
+        host: &(dyn Any + Send + Sync),
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        let pending = self.prepare_create(name, false)?;
+
+        info!("Creating module with context: {}", name);
+
+        self.invoke_factory_with_host(name, &pending, Some(host))
+    }
+
+    /// Create a module instance, retrying failures for which `retry_pred` returns true
+    ///
+    /// Makes at most `attempts` attempts (at least one) with no delay between them.
+    /// Returns the first error `retry_pred` rejects, or the last error once attempts run out.
+    pub fn create_any_retry_if(
+        &self,
+        name: &str,
+        attempts: u32,
+        retry_pred: impl Fn(&anyhow::Error) -> bool,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        let mut attempt = 1;
+        loop {
+            match self.create_any(name) {
+                Ok(instance) => return Ok(instance),
+                Err(e) if attempt < attempts && retry_pred(&e) => {
+                    warn!("Retrying module {} after attempt {}: {:#}", name, attempt, e);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Create a shared module instance by name
+    ///
+    /// Singleton modules return a clone of their single instance, created on first use.
+    /// Other modules return a fresh instance on every call.
+    pub fn create_shared(&self, name: &str) -> Result<Arc<dyn Any + Send + Sync>> {
+        let pending = self.lookup_create(name, true)?;
+
+        if !pending.singleton {
+            self.take_rate_token(name, &pending)?;
+            info!("Creating module: {}", name);
+            return self
+                .invoke_factory(name, &pending)
+                .map(Arc::from);
+        }
+
+        self.cached_instance(name, &pending)
+    }
+
+    /// Get a module's instance, creating it exactly once on first use
+    ///
+    /// Concurrent first callers wait for a single factory call and share its result;
+    /// later callers get the cached `Arc` without taking a rate-limit token or running
+    /// the precondition. If the factory fails or panics, the next call retries. A
+    /// factory that asks for its own module fails with `RegistryError::DependencyCycle`.
+    /// Works for any module; for singletons this is the same instance `create_shared`
+    /// returns. The instance is kept until the module is registered again.
+    pub fn get_lazy(&self, name: &str) -> Result<Arc<dyn Any + Send + Sync>> {
+        let pending = self.lookup_create(name, true)?;
+        self.cached_instance(name, &pending)
+    }
+
+    /// Return the instance cached in the module's slot, creating it on a miss
+    ///
+    /// Only a miss takes a rate-limit token and calls the factory.
+    fn cached_instance(
+        &self,
+        name: &str,
+        pending: &PendingCreate,
+    ) -> Result<Arc<dyn Any + Send + Sync>> {
+        if let Some(existing) = pending.instance.get() {
+            return Ok(existing);
+        }
+
+        pending.instance.get_or_try_init(name, || {
+            self.take_rate_token(name, pending)?;
+            info!("Creating shared instance of module: {}", name);
+            self.invoke_factory(name, pending).map(Arc::from)
+        })
+    }
+
+    /// Create a module instance counted against its live instance cap
+    ///
+    /// The instance is released when the returned guard drops. Fails with
+    /// `RegistryError::TooManyInstances` once `max_live_instances` guards are alive.
+    /// Instances from other create paths are not counted.
+    pub fn create_guarded(&self, name: &str) -> Result<GuardedInstance> {
+        let pending = self.lookup_create(name, false)?;
+
+        let reserved = pending
+            .live
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |live| {
+                match pending.max_live {
+                    Some(max) if live >= max => None,
+                    _ => Some(live + 1),
+                }
+            });
+        if reserved.is_err() {
+            return Err(RegistryError::TooManyInstances {
+                name: name.to_string(),
+                max: pending.max_live.unwrap_or_default(),
+            }
+            .into());
+        }
+
+        if let Err(e) = self.take_rate_token(name, &pending) {
+            pending.live.fetch_sub(1, Ordering::AcqRel);
+            return Err(e.into());
+        }
+
+        info!("Creating guarded module: {}", name);
+        match self.invoke_factory(name, &pending) {
+            Ok(instance) => Ok(GuardedInstance {
+                instance,
+                live: pending.live,
+            }),
+            Err(e) => {
+                pending.live.fetch_sub(1, Ordering::AcqRel);
+                Err(e)
+            }
+        }
+    }
+
+    /// Set the maximum number of live guarded instances of a module, or `None` for no cap
+    pub fn set_max_live_instances(&self, name: &str, max: Option<usize>) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        metadata.max_live_instances = max;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Set max live instances for module {}: {:?}", name, max);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Set a validator run on every new instance of a module, or `None` to remove it
+    ///
+    /// Instances the validator rejects are dropped and creation fails with
+    /// `RegistryError::InvalidInstance`, e.g. to refuse a factory returning `()`.
+    pub fn set_instance_validator(
+        &self,
+        name: &str,
+        validator: Option<InstanceValidator>,
+    ) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let entry = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        entry.validator = validator;
+        info!("Set instance validator for module: {}", name);
+        Ok(())
+    }
+
+    /// Set the finalizer run on instances of a module by `finalize`
+    pub fn register_finalizer(&self, name: &str, finalizer: Finalizer) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let entry = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        entry.finalizer = Some(finalizer);
+        info!("Set finalizer for module: {}", name);
+        Ok(())
+    }
+
+    /// Run a module's finalizer on an instance of it
+    ///
+    /// Succeeds without doing anything if the module has no finalizer. The finalizer
+    /// runs outside the registry lock.
+    pub fn finalize(&self, name: &str, instance: &(dyn Any + Send + Sync)) -> Result<()> {
+        let finalizer = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?
+            .finalizer;
+
+        match finalizer {
+            Some(finalizer) => {
+                finalizer(instance).with_context(|| format!("Failed to finalize module: {}", name))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Look up a module for creation and apply per-module creation checks
+    ///
+    /// The returned handles let the factory run after the registry lock is released.
+    fn prepare_create(&self, name: &str, allow_singleton: bool) -> Result<PendingCreate> {
+        let pending = self.lookup_create(name, allow_singleton)?;
+        self.take_rate_token(name, &pending)?;
+        Ok(pending)
+    }
+
+    /// Look up a module for creation without taking a rate-limit token
+    ///
+    /// Callers with further refusals of their own take the token after those.
+    fn lookup_create(&self, name: &str, allow_singleton: bool) -> Result<PendingCreate> {
+        let key = self.key(name);
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let entry = modules
+            .get(key.as_ref())
+            .ok_or_else(|| not_found_with_candidates(&modules, name))?;
+
+        self.check_create(name, &key, entry, allow_singleton)
+    }
+
+    /// Create a module by its stored registry key, without normalizing it again
+    ///
+    /// Bulk paths iterate stored keys, which a non-idempotent key normalizer would
+    /// otherwise map to a different key.
+    fn create_by_key(&self, key: &str) -> Result<Box<dyn Any + Send + Sync>> {
+        let pending = {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            let entry = modules.get(key).ok_or_else(|| module_not_found(key))?;
+            self.check_create(key, key, entry, false)?
+        };
+        self.take_rate_token(key, &pending)?;
+
+        info!("Creating module: {}", key);
+
+        self.invoke_factory(key, &pending)
+    }
+
+    /// Take a creation token, refusing with `RegistryError::RateLimited` when none is left
+    ///
+    /// Runs after every other creation check so that refused creations leave the bucket alone.
+    fn take_rate_token(&self, name: &str, pending: &PendingCreate) -> Result<(), RegistryError> {
+        if pending.acquire_rate_token(self.now()) {
+            Ok(())
+        } else {
+            Err(RegistryError::RateLimited {
+                name: name.to_string(),
+            })
+        }
+    }
+
+    /// Apply per-module creation checks to the entry stored under `key`
+    ///
+    /// Rate limiting is left to `take_rate_token`.
+    fn check_create(
+        &self,
+        name: &str,
+        key: &str,
+        entry: &ModuleEntry,
+        allow_singleton: bool,
+    ) -> Result<PendingCreate> {
+        if entry.reserved {
+            return Err(RegistryError::NotReady(name.to_string()).into());
+        }
+
+        if !entry.metadata.enabled {
+            return Err(RegistryError::Disabled(name.to_string()).into());
+        }
+
+        if entry.metadata.singleton && !allow_singleton {
+            return Err(RegistryError::Singleton(name.to_string()).into());
+        }
+
+        if let Some(note) = &entry.metadata.deprecated {
+            if self.deny_deprecated.load(Ordering::Relaxed) {
+                return Err(RegistryError::Deprecated {
+                    name: name.to_string(),
+                    note: note.clone(),
+                }
+                .into());
+            }
+            warn!("Creating deprecated module: {} ({})", name, note);
+        }
+
+        Ok(PendingCreate {
+            key: key.to_string(),
+            factory: entry.factory,
+            stats: entry.stats.clone(),
+            singleton: entry.metadata.singleton,
+            instance: entry.instance.clone(),
+            expected_type_id: entry.metadata.expected_type_id,
+            live: entry.live.clone(),
+            max_live: entry.metadata.max_live_instances,
+            precondition: entry.precondition,
+            context_factory: entry.context_factory,
+            validator: entry.validator,
+            rate_limit: entry.metadata.rate_limit,
+            rate_bucket: entry.rate_bucket.clone(),
+        })
+    }
+
+    /// Create every module whose metadata matches the predicate
+    ///
+    /// Matching names are collected under the read lock; modules are created after it is released.
+    pub fn create_where(
+        &self,
+        pred: impl Fn(&ModuleMetadata) -> bool,
+    ) -> Vec<(String, Result<Box<dyn Any + Send + Sync>>)> {
+        let selected: Vec<String> = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| pred(&entry.metadata))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        selected
+            .into_iter()
+            .map(|name| {
+                let result = self.create_by_key(&name);
+                (name, result)
+            })
+            .collect()
+    }
+
+    /// Create every module in name order and pass each instance to a visitor
+    ///
+    /// Factories run without holding the registry lock, and each instance is dropped
+    /// after `f` returns. Modules that fail to create are reported and not visited.
+    /// Use `downcast_module` inside the visitor to reach a double-boxed trait object.
+    pub fn visit_all<F: FnMut(&str, &(dyn Any + Send + Sync))>(
+        &self,
+        mut f: F,
+    ) -> Vec<(String, Result<()>)> {
+        self.list_modules_sorted()
+            .into_iter()
+            .map(|name| {
+                let result = self
+                    .create_by_key(&name)
+                    .map(|instance| f(&name, &*instance));
+                (name, result)
+            })
+            .collect()
+    }
+
+    /// Create a module and all of its transitive dependencies, dependencies first
+    ///
+    /// Missing hard dependencies, cycles and over-deep chains are reported before any module is created.
+    pub fn create_chain(&self, name: &str) -> Result<Vec<(String, Box<dyn Any + Send + Sync>)>> {
+        let order = {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            let key = self.key(name);
+            if !modules.contains_key(key.as_ref()) {
+                return Err(not_found_with_candidates(&modules, name));
+            }
+            self.dependency_order(&modules, &HashMap::new(), [key.as_ref()])?
+        };
+
+        order
+            .into_iter()
+            .map(|name| {
+                let instance = self.create_by_key(&name)?;
+                Ok((name, instance))
+            })
+            .collect()
+    }
+
+    /// Verify every requested module against a policy, then create them all
+    ///
+    /// If any module is not registered or fails verification, nothing is created and
+    /// the error is a `RegistryError::TransactionAborted` listing each failure. Modules
+    /// are created in the order given; a factory error after verification drops the
+    /// instances created so far.
+    pub fn create_all_transactional(
+        &self,
+        names: &[&str],
+        policy: &SecurityPolicy,
+    ) -> Result<Vec<(String, Box<dyn Any + Send + Sync>)>> {
+        {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            let mut failures = Vec::new();
+
+            for name in names {
+                let Some(entry) = modules.get(self.key(name).as_ref()) else {
+                    failures.push(RegistryError::NotFound(name.to_string()));
+                    continue;
+                };
+
+                let check =
+                    SecurityValidator::comprehensive_check_with_policy(&entry.metadata, policy);
+                let blocking = policy.blocking_issues(&check);
+                if !blocking.is_empty() {
+                    failures.push(RegistryError::SecurityCheckFailed {
+                        name: name.to_string(),
+                        reasons: blocking.iter().map(|i| i.message.clone()).collect(),
+                    });
+                }
+            }
+
+            if !failures.is_empty() {
+                return Err(RegistryError::TransactionAborted { failures }.into());
+            }
+        }
+
+        names
+            .iter()
+            .map(|name| Ok((name.to_string(), self.create_any(name)?)))
+            .collect()
+    }
+
+    /// Define a named group of modules that can be created together
+    ///
+    /// Every member must already be registered. Redefining a group replaces its members.
+    /// Groups are not updated when members are unregistered; `create_group` then fails.
+    pub fn define_group(&self, group: &str, members: Vec<String>) -> Result<()> {
+        let group = validated_name(group)?;
+        {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            for member in &members {
+                if !modules.contains_key(self.key(member).as_ref()) {
+                    return Err(not_found_with_candidates(&modules, member))
+                        .with_context(|| format!("Invalid member of group: {}", group));
+                }
+            }
+        }
+
+        info!("Defined group {} with {} members", group, members.len());
+        self.groups
+            .write()
+            .expect("Failed to acquire write lock")
+            .insert(group.to_string(), members);
+        Ok(())
+    }
+
+    /// Create every member of a group, in the order the group was defined
+    ///
+    /// Stops at the first failure, dropping the instances created so far.
+    pub fn create_group(&self, group: &str) -> Result<Vec<(String, Box<dyn Any + Send + Sync>)>> {
+        let members = self
+            .groups
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(group.trim())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Group not found: {}",
+                    truncate_name_for_display(group, MAX_DISPLAY_NAME_LENGTH)
+                )
+            })?;
+
+        members
+            .into_iter()
+            .map(|name| {
+                let instance = self.create_any(&name)?;
+                Ok((name, instance))
+            })
+            .collect()
+    }
+
+    /// Get all defined group names, sorted
+    pub fn list_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = self
+            .groups
+            .read()
+            .expect("Failed to acquire read lock")
+            .keys()
+            .cloned()
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    /// Invoke a factory, routing through the interceptor if one is set
+    fn invoke_factory(
+        &self,
+        name: &str,
+        pending: &PendingCreate,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        self.invoke_factory_with_host(name, pending, None)
+    }
+
+    /// Invoke a factory, passing `host` to context factories
+    ///
+    /// Context factories bypass the interceptor, which only wraps plain factories.
+    fn invoke_factory_with_host(
+        &self,
+        name: &str,
+        pending: &PendingCreate,
+        host: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        if let Some(precondition) = pending.precondition {
+            precondition().map_err(|error| RegistryError::PreconditionFailed {
+                name: name.to_string(),
+                error,
+            })?;
+        }
+
+        let interceptor = self
+            .interceptor
+            .read()
+            .expect("Failed to acquire read lock")
+            .clone();
+
+        let started = Instant::now();
+        let instance = match (pending.context_factory, interceptor) {
+            (Some(factory), _) => factory(&RegistryContext {
+                registry: self,
+                name,
+                key: &pending.key,
+                host,
+            }),
+            (None, Some(interceptor)) => interceptor(name, pending.factory),
+            (None, None) => (pending.factory)(),
+        };
+        let instance = match instance {
+            Ok(instance) => instance,
+            Err(e) => {
+                self.record_factory_failure(name, pending);
+                #[cfg(feature = "backtrace")]
+                return Err(RegistryError::FactoryFailed {
+                    name: name.to_string(),
+                    source: e,
+                    backtrace: Box::new(std::backtrace::Backtrace::force_capture()),
+                }
+                .into());
+                #[cfg(not(feature = "backtrace"))]
+                return Err(e).with_context(|| format!("Failed to instantiate module: {}", name));
+            }
+        };
+
+        if let Some(expected) = pending.expected_type_id {
+            if (*instance).type_id() != expected {
+                return Err(RegistryError::TypeAssertionFailed(name.to_string()).into());
+            }
+        }
+
+        if let Some(validator) = pending.validator {
+            validator(&*instance).map_err(|error| RegistryError::InvalidInstance {
+                name: name.to_string(),
+                error,
+            })?;
+        }
+
+        pending.stats.record(started.elapsed(), self.timestamp());
+        Ok(instance)
+    }
+
+    /// Count a factory failure and open the circuit once the threshold is reached
+    fn record_factory_failure(&self, name: &str, pending: &PendingCreate) {
+        let failures = pending.stats.record_failure();
+        let threshold = self.failure_threshold.load(Ordering::Relaxed);
+        if threshold == 0 || failures != threshold {
+            return;
+        }
+
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let Some(entry) = modules.get_mut(&pending.key) else {
+            return;
+        };
+        entry.metadata.enabled = false;
+        entry.metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        warn!(
+            "Disabled module {} after {} consecutive factory failures",
+            name, failures
+        );
+        self.events.emit(RegistryEvent::CircuitOpened {
+            name: name.to_string(),
+        });
+    }
+
+    /// Create every registered module using at most `max_concurrent` threads at a time
+    ///
+    /// Factories run without holding the registry lock. Results are returned per module.
+    pub fn create_all_throttled(
+        &self,
+        max_concurrent: usize,
+    ) -> Vec<(String, Result<Box<dyn Any + Send + Sync>>)> {
+        let pending = self.list_modules();
+
+        let workers = max_concurrent.max(1).min(pending.len());
+        let queue = Mutex::new(pending.into_iter());
+        let results = Mutex::new(Vec::new());
+
+        info!("Creating all modules with up to {} concurrent factories", workers);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let next = queue.lock().expect("Failed to acquire queue lock").next();
+                    let Some(name) = next else {
+                        break;
+                    };
+                    let result = self.create_by_key(&name);
+                    results
+                        .lock()
+                        .expect("Failed to acquire results lock")
+                        .push((name, result));
+                });
+            }
+        });
+
+        results.into_inner().expect("Failed to acquire results lock")
+    }
+
+    /// Set an interceptor invoked by `create_any` in place of calling the factory directly
+    pub fn set_factory_interceptor(&self, interceptor: FactoryInterceptor) {
+        *self.interceptor.write().expect("Failed to acquire write lock") = Some(interceptor);
+    }
+
+    /// Remove the factory interceptor, restoring direct factory calls
+    pub fn clear_factory_interceptor(&self) {
+        *self.interceptor.write().expect("Failed to acquire write lock") = None;
+    }
+
+    /// Create and downcast a module to a specific trait type
+    ///
+    /// On a mismatch, the error names the requested type and hints at the double-box
+    /// convention, pointing out an extra outer box when there is one.
+    pub fn create<T: 'static>(&self, name: &str) -> Result<Box<T>> {
+        let any_module = self.create_any(name)?;
+
+        any_module.downcast::<T>().map_err(|instance| {
+            anyhow::anyhow!(
+                "Module type mismatch for: {} (requested {}). {}",
+                truncate_name_for_display(name, MAX_DISPLAY_NAME_LENGTH),
+                std::any::type_name::<T>(),
+                double_box_hint::<T>(&*instance)
+            )
+        })
+    }
+
+    /// Create a module instance, or use `fallback` if it is missing or its factory fails
+    pub fn create_or_else(
+        &self,
+        name: &str,
+        fallback: impl FnOnce() -> Box<dyn Any + Send + Sync>,
+    ) -> Box<dyn Any + Send + Sync> {
+        self.create_any(name).unwrap_or_else(|_| fallback())
+    }
+
+    /// Check whether a module's instance downcasts to `T`
+    ///
+    /// This runs the factory, with all its side effects, and drops the instance.
+    /// Returns `false` if the module is missing or its factory fails.
+    pub fn can_create_as<T: 'static>(&self, name: &str) -> bool {
+        self.create_any(name)
+            .map(|instance| instance.downcast::<T>().is_ok())
+            .unwrap_or(false)
+    }
+
+    /// Get all registered module names
+    ///
+    /// Names are in unspecified order; use `list_modules_sorted` for a stable order.
+    pub fn list_modules(&self) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Get all registered module names, sorted lexicographically
+    pub fn list_modules_sorted(&self) -> Vec<String> {
+        let mut names = self.list_modules();
+        names.sort_unstable();
+        names
+    }
+
+    /// Get all registered module names ordered by `key`, ties broken by name
+    ///
+    /// Replacing a module counts as a new registration; renaming keeps its place.
+    pub fn list_sorted_by(&self, key: SortKey) -> Vec<String> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let mut entries: Vec<(&String, &ModuleEntry)> = modules.iter().collect();
+        entries.sort_unstable_by(|(a_name, a), (b_name, b)| {
+            let order = match key {
+                SortKey::Name => std::cmp::Ordering::Equal,
+                SortKey::Type => a.metadata.module_type.cmp(&b.metadata.module_type),
+                SortKey::StructName => a.metadata.struct_name.cmp(&b.metadata.struct_name),
+                SortKey::RegistrationIndex => a.registration_index.cmp(&b.registration_index),
+            };
+            order.then_with(|| a_name.cmp(b_name))
+        });
+        entries.into_iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Get names of enabled modules
+    pub fn list_enabled(&self) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| entry.metadata.enabled)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Get `(name, module_type)` pairs for all registered modules
+    pub fn list_with_types(&self) -> Vec<(String, String)> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .map(|(name, entry)| (name.clone(), entry.metadata.module_type.clone()))
+            .collect()
+    }
+
+    /// Call `f` on the metadata of every module, stopping at the first error
+    ///
+    /// The read lock is held for the whole iteration, so `f` must not call back into
+    /// the registry; a write from inside `f` deadlocks. Iteration order is unspecified.
+    pub fn try_for_each_metadata<E>(
+        &self,
+        mut f: impl FnMut(&ModuleMetadata) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .values()
+            .try_for_each(|entry| f(&entry.metadata))
+    }
+
+    /// Get all registered module names (alias for compatibility)
+    pub fn get_module_names(&self) -> Vec<String> {
+        self.list_modules()
+    }
+
+    /// Check if a module is registered
+    pub fn has_module(&self, name: &str) -> bool {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .contains_key(self.key(name).as_ref())
+    }
+
+    /// Get metadata for a module
+    pub fn get_metadata(&self, name: &str) -> Option<ModuleMetadata> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .map(|entry| entry.metadata.clone())
+    }
+
+    /// Describe a module: metadata plus derived runtime state
+    pub fn describe(&self, name: &str) -> Option<ModuleDescription> {
+        let key = self.key(name);
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let aliases = self.aliases.read().expect("Failed to acquire read lock");
+        modules
+            .get(key.as_ref())
+            .map(|entry| entry.describe(&key, &aliases))
+    }
+
+    /// Describe all modules, sorted by name
+    pub fn describe_all(&self) -> Vec<ModuleDescription> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let aliases = self.aliases.read().expect("Failed to acquire read lock");
+        let mut descriptions: Vec<ModuleDescription> = modules
+            .iter()
+            .map(|(key, entry)| entry.describe(key, &aliases))
+            .collect();
+        drop(aliases);
+        drop(modules);
+
+        descriptions.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        descriptions
+    }
+
+    /// Get creation statistics for a module
+    pub fn creation_stats(&self, name: &str) -> Option<CreationStats> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .map(|entry| entry.stats.snapshot())
+    }
+
+    /// Get names of modules that were never created since registration or the last `reset_stats`
+    pub fn unused_modules(&self) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| entry.stats.count.load(Ordering::Relaxed) == 0)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Reset creation counters and timings for all modules
+    ///
+    /// Unlike `clear`, registered modules are kept.
+    pub fn reset_stats(&self) {
+        for entry in self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .values()
+        {
+            entry.stats.reset();
+        }
+
+        info!("Reset creation stats");
+    }
+
+    /// Clear all registered modules (for testing)
+    pub fn clear(&self) {
+        self.modules
+            .write()
+            .expect("Failed to acquire write lock")
+            .clear();
+
+        self.events.emit(RegistryEvent::Cleared);
+    }
+
+    /// Replace the whole module set atomically
+    ///
+    /// Every entry is validated and the new map is built before taking the write lock, so
+    /// readers see either the full old set or the full new set. If any entry fails
+    /// validation, the registry is left unchanged. Stats and runtime state are discarded.
+    /// Fails with `RegistryError::SecuritySealed` once security metadata is sealed.
+    pub fn replace_all(&self, entries: Vec<(ModuleMetadata, ModuleFactory)>) -> Result<()> {
+        self.check_unsealed("all modules")?;
+        let mut replacement = HashMap::with_capacity(entries.len());
+        let mut registered = Vec::with_capacity(entries.len());
+
+        for (mut metadata, factory) in entries {
+            let name = self.check_metadata(&mut metadata, &EntryExtras::prebuilt())?;
+            let key = self.key(&name).into_owned();
+            registered.push((name, metadata.module_type.clone()));
+            replacement.insert(key, ModuleEntry::new(metadata, factory));
+        }
+
+        let previous = std::mem::replace(
+            &mut *self.modules.write().expect("Failed to acquire write lock"),
+            replacement,
+        );
+        drop(previous);
+
+        info!("Replaced all modules with {} new modules", registered.len());
+        self.events.emit(RegistryEvent::Cleared);
+        for (name, module_type) in registered {
+            self.events.emit(RegistryEvent::Registered { name, module_type });
+        }
+        Ok(())
+    }
+
+    /// Subscribe to registry events
+    ///
+    /// Events are delivered outside the registry lock to every subscriber.
+    pub fn subscribe(&self) -> EventReceiver {
+        self.events.subscribe()
+    }
+
+    /// Get count of registered modules
+    pub fn count(&self) -> usize {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .len()
+    }
+
+    /// Get names of modules requesting the given permission
+    pub fn modules_requiring(&self, perm: Permission) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| entry.metadata.permissions.grants(perm))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Get the permissions in `required` that a module does not request
+    ///
+    /// Permissions are returned in the order given, without duplicates.
+    pub fn missing_permissions(
+        &self,
+        name: &str,
+        required: &[Permission],
+    ) -> Result<Vec<Permission>> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        let mut missing = Vec::new();
+        for &perm in required {
+            if !metadata.permissions.grants(perm) && !missing.contains(&perm) {
+                missing.push(perm);
+            }
+        }
+
+        Ok(missing)
+    }
+
+    /// Get the saturating sum of every module's `memory_limit_mb`
+    pub fn total_memory_budget_mb(&self) -> u64 {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .values()
+            .fold(0u64, |total, entry| {
+                total.saturating_add(entry.metadata.permissions.memory_limit_mb)
+            })
+    }
+
+    /// Check if all modules running at their memory limit would exceed `host_mb`
+    pub fn over_budget(&self, host_mb: u64) -> bool {
+        self.total_memory_budget_mb() > host_mb
+    }
+
+    /// Get names of modules requesting `system_access` or `process_spawn`
+    pub fn high_privilege_modules(&self) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| {
+                entry.metadata.permissions.grants(Permission::SystemAccess)
+                    || entry.metadata.permissions.grants(Permission::ProcessSpawn)
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Compute a stable fingerprint of the registered metadata set
+    ///
+    /// Hashes the sorted `(name, module_type, struct_name, review status)` tuples with SHA-256,
+    /// so registries with identical metadata match regardless of insertion order or process.
+    pub fn fingerprint(&self) -> u64 {
+        let mut tuples: Vec<(String, String, String, &'static str)> = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .map(|(name, entry)| {
+                (
+                    name.clone(),
+                    entry.metadata.module_type.clone(),
+                    entry.metadata.struct_name.clone(),
+                    entry.metadata.review_status.as_str(),
+                )
+            })
+            .collect();
+        tuples.sort();
+
+        let mut hasher = Sha256::new();
+        for (name, module_type, struct_name, status) in &tuples {
+            for field in [name.as_str(), module_type, struct_name, status] {
+                hasher.update((field.len() as u64).to_le_bytes());
+                hasher.update(field.as_bytes());
+            }
+        }
+
+        let digest = hasher.finalize();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Check whether the registry differs from a previously captured `fingerprint`
+    ///
+    /// Only changes covered by the fingerprint are detected; permission or enablement
+    /// changes, for example, are not.
+    pub fn changed_since(&self, previous: u64) -> bool {
+        self.fingerprint() != previous
+    }
+
+    /// Verify module signature
+    pub fn verify_module_signature(&self, name: &str) -> Result<bool> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        SecurityValidator::verify_signature(metadata)
+    }
+
+    /// Verify the signature of every module
+    ///
+    /// Metadata of signed modules is snapshotted under the read lock and verified after
+    /// it is released. Unsigned modules and verification errors map to `false`.
+    pub fn verify_all_signatures(&self) -> HashMap<String, bool> {
+        let mut results = HashMap::new();
+        let mut signed = Vec::new();
+        {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            for (name, ModuleEntry { metadata, .. }) in modules.iter() {
+                if metadata.signature.is_some() {
+                    signed.push((name.clone(), metadata.clone()));
+                } else {
+                    results.insert(name.clone(), false);
+                }
+            }
+        }
+
+        for (name, metadata) in signed {
+            let verified = SecurityValidator::verify_signature(&metadata).unwrap_or(false);
+            results.insert(name, verified);
+        }
+        results
+    }
+
+    /// Check if module has required permissions
+    pub fn check_module_permissions(&self, name: &str, required_permission: &str) -> Result<bool> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        SecurityValidator::check_permissions(metadata, required_permission)
+    }
+
+    /// Check if module passed code review
+    pub fn is_module_approved(&self, name: &str) -> Result<bool> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        SecurityValidator::is_approved(metadata)
+    }
+
+    /// Get the code review status of a module
+    pub fn review_info(&self, name: &str) -> Option<CodeReviewStatus> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .map(|entry| entry.metadata.review_status.clone())
+    }
+
+    /// Get names of modules whose review status matches the given one
+    ///
+    /// Only the status kind is compared; reviewer, reason and timestamp are ignored.
+    pub fn list_by_review_status(&self, status: &CodeReviewStatus) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| entry.metadata.review_status.as_str() == status.as_str())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Count modules per review status, keyed by `CodeReviewStatus::as_str`
+    pub fn review_status_counts(&self) -> HashMap<String, usize> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let mut counts = HashMap::new();
+
+        for entry in modules.values() {
+            *counts
+                .entry(entry.metadata.review_status.as_str().to_string())
+                .or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Render all modules as an aligned text table sorted by name
+    ///
+    /// Columns are Name, Type, Struct, Approved and Signed. Widths count `char`s, so
+    /// wide or combining Unicode characters can misalign the columns.
+    pub fn to_table(&self) -> String {
+        let mut rows: Vec<[String; 5]> = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .map(|(name, entry)| {
+                let metadata = &entry.metadata;
+                let yes_no = |flag: bool| if flag { "yes" } else { "no" }.to_string();
+                [
+                    name.clone(),
+                    metadata.module_type.clone(),
+                    metadata.struct_name.clone(),
+                    yes_no(metadata.is_approved()),
+                    yes_no(metadata.has_valid_signature()),
+                ]
+            })
+            .collect();
+        rows.sort_unstable();
+
+        let header = ["Name", "Type", "Struct", "Approved", "Signed"].map(String::from);
+        let mut widths = [0; 5];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let render = |row: &[String; 5]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            cells.join(" | ").trim_end().to_string()
+        };
+
+        let mut lines = vec![render(&header)];
+        lines.push(
+            widths
+                .iter()
+                .map(|&width| "-".repeat(width))
+                .collect::<Vec<_>>()
+                .join("-+-"),
+        );
+        lines.extend(rows.iter().map(render));
+        lines.join("\n")
+    }
+
+    /// Group modules into a tree by their dot-delimited module type
+    pub fn type_tree(&self) -> TypeTreeNode {
+        let mut modules = self.list_with_types();
+        modules.sort_unstable();
+
+        let mut root = TypeTreeNode::default();
+        for (name, module_type) in modules {
+            let node = module_type.split('.').fold(&mut root, |node, segment| {
+                node.children.entry(segment.to_string()).or_default()
+            });
+            node.modules.push(name);
+        }
+        root
+    }
+
+    /// Count registrations per `struct_name`
+    ///
+    /// A generic factory often backs several names, so counts can exceed one.
+    pub fn struct_name_histogram(&self) -> HashMap<String, usize> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let mut counts = HashMap::new();
+
+        for entry in modules.values() {
+            *counts
+                .entry(entry.metadata.struct_name.clone())
+                .or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Get aggregate counts and security posture in a single read-lock pass
+    pub fn status_snapshot(&self) -> RegistryStatus {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let mut status = RegistryStatus {
+            total_modules: modules.len(),
+            ..RegistryStatus::default()
+        };
+
+        for ModuleEntry { metadata, .. } in modules.values() {
+            *status.by_type.entry(metadata.module_type.clone()).or_insert(0) += 1;
+
+            match metadata.review_status {
+                CodeReviewStatus::Pending => status.pending += 1,
+                CodeReviewStatus::InProgress => status.in_progress += 1,
+                CodeReviewStatus::Approved { .. } => status.approved += 1,
+                CodeReviewStatus::Rejected { .. } => status.rejected += 1,
+            }
+
+            if !SecurityValidator::comprehensive_check(metadata).is_secure {
+                status.insecure += 1;
+            }
+            if !metadata.enabled {
+                status.disabled += 1;
+            }
+        }
+
+        status
+    }
+
+    /// Verify supply chain information
+    pub fn verify_supply_chain(&self, name: &str) -> Result<bool> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        SecurityValidator::verify_supply_chain(metadata)
+    }
+
+    /// Create module with security checks
+    pub fn create_secure(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
+        // Verify signature
+        if !self.verify_module_signature(name)? {
+            return Err(anyhow::anyhow!("Module signature verification failed: {}", name));
+        }
+
+        // Check if module is approved
+        if !self.is_module_approved(name)? {
+            return Err(anyhow::anyhow!("Module not approved: {}", name));
+        }
+
+        // Verify supply chain
+        if !self.verify_supply_chain(name)? {
+            return Err(anyhow::anyhow!("Supply chain verification failed: {}", name));
+        }
+
+        // Create module with sandboxing
+        self.create_with_sandbox(name)
+    }
+
+    /// Create module with sandbox configuration
+    pub fn create_with_sandbox(&self, name: &str) -> Result<Box<dyn Any + Send + Sync>> {
+        {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            let ModuleEntry { metadata, .. } = modules
+                .get(self.key(name).as_ref())
+                .ok_or_else(|| module_not_found(name))?;
+
+            // Apply sandbox configuration
+            if metadata.sandbox_config.enabled {
+                info!("Creating sandboxed module: {}", name);
+                // In a real implementation, set up sandbox environment
+                // For now, just log the sandbox config
+                info!("Sandbox config: {:?}", metadata.sandbox_config);
+            }
+        }
+
+        self.create_any(name)
+    }
+
+    /// Add a named permission preset, shadowing any built-in preset of the same name
+    pub fn register_preset(&self, name: &str, permissions: ModulePermissions) -> Result<()> {
+        let name = validated_name(name)?;
+        self.presets
+            .write()
+            .expect("Failed to acquire write lock")
+            .insert(name.to_string(), permissions);
+
+        info!("Registered permission preset: {}", name);
+        Ok(())
+    }
+
+    /// Get a permission preset, looking up registered presets before built-in ones
+    pub fn preset(&self, name: &str) -> Option<ModulePermissions> {
+        self.presets
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(name.trim())
+            .cloned()
+            .or_else(|| ModulePermissions::preset(name.trim()))
+    }
+
+    /// Register a module with the permissions of a named preset
+    ///
+    /// Fails with `RegistryError::UnknownPreset` if `preset` is neither registered with
+    /// `register_preset` nor one of `ModulePermissions::PRESETS`.
+    pub fn register_with_preset(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+        preset: &str,
+    ) -> Result<()> {
+        let permissions = self
+            .preset(preset)
+            .ok_or_else(|| RegistryError::UnknownPreset(preset.to_string()))?;
+
+        let mut metadata = registry_metadata(name, module_type, "factory");
+        metadata.permissions = permissions;
+        self.insert_entry(metadata, factory, EntryExtras::default())
+    }
+
+    /// Forbid further changes to the security metadata of registered modules
+    ///
+    /// Afterwards `update_permissions`, `update_review_status`, `update_signature`,
+    /// `update_sandbox_config`, `replace_all`, and every registration that would replace
+    /// an existing module fail with `RegistryError::SecuritySealed`. New modules can still
+    /// be registered. Sealing cannot be undone.
+    pub fn seal_security(&self) {
+        self.security_sealed.store(true, Ordering::Release);
+        info!("Sealed module security metadata");
+    }
+
+    /// Check whether security metadata is sealed
+    pub fn is_security_sealed(&self) -> bool {
+        self.security_sealed.load(Ordering::Acquire)
+    }
+
+    /// Check and insert a module, then announce it
+    ///
+    /// Every register path goes through here so that name, type, path, security and
+    /// seal checks apply uniformly. Replacing a module fails once security is sealed.
+    fn insert_entry(
+        &self,
+        mut metadata: ModuleMetadata,
+        factory: ModuleFactory,
+        extras: EntryExtras,
+    ) -> Result<()> {
+        let name = self.check_metadata(&mut metadata, &extras)?;
+        let key = self.key(&name).into_owned();
+        let module_type = metadata.module_type.clone();
+
+        let mut entry = ModuleEntry::new(metadata, factory);
+        entry.schema = extras.schema;
+        entry.precondition = extras.precondition;
+        entry.context_factory = extras.context_factory;
+        entry.reserved = extras.reserved;
+        #[cfg(feature = "wasm")]
+        {
+            entry.wasm = extras.wasm;
+        }
+
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        if modules.contains_key(&key) {
+            self.check_unsealed(&name)?;
+        }
+        modules.insert(key, entry);
+        drop(modules);
+
+        info!("Registered module: {} (type: {})", name, module_type);
+        self.events.emit(RegistryEvent::Registered { name, module_type });
+        Ok(())
+    }
+
+    /// Fail with `RegistryError::SecuritySealed` once `seal_security` was called
+    fn check_unsealed(&self, name: &str) -> Result<(), RegistryError> {
+        if self.is_security_sealed() {
+            return Err(RegistryError::SecuritySealed(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Register module with security metadata
+    pub fn register_secure(
+        &self,
+        name: &str,
+        module_type: &str,
+        factory: ModuleFactory,
+        signature: Option<ModuleSignature>,
         permissions: ModulePermissions,
         supply_chain: Option<SupplyChainInfo>,
-    ) {
+    ) -> Result<()> {
         let metadata = ModuleMetadata::secure(
             name.to_string(),
             module_type.to_string(),
@@ -274,63 +2510,1014 @@ impl ModuleRegistry {
             permissions,
             supply_chain,
         );
+        let extras = EntryExtras {
+            verify: true,
+            ..EntryExtras::default()
+        };
+        self.insert_entry(metadata, factory, extras)
+    }
+
+    /// Update code review status
+    pub fn update_review_status(
+        &self,
+        name: &str,
+        status: CodeReviewStatus,
+    ) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        self.check_unsealed(name)?;
+
+        set_review_status(metadata, status, self.timestamp());
+        drop(modules);
+
+        info!("Updated review status for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Update code review status only if the current status is still `expected`
+    ///
+    /// Statuses are compared by variant, ignoring reviewer, timestamps and reasons.
+    /// Returns `Ok(false)` without changing anything if the status no longer matches.
+    pub fn compare_and_set_review(
+        &self,
+        name: &str,
+        expected: &CodeReviewStatus,
+        new: CodeReviewStatus,
+    ) -> Result<bool> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        self.check_unsealed(name)?;
+
+        if std::mem::discriminant(&metadata.review_status) != std::mem::discriminant(expected) {
+            return Ok(false);
+        }
+        set_review_status(metadata, new, self.timestamp());
+        drop(modules);
+
+        info!("Updated review status for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(true)
+    }
+
+    /// Replace the signature of a module
+    ///
+    /// An existing approval is kept; use `stale_approvals` to find approvals made for
+    /// a different code hash.
+    pub fn update_signature(&self, name: &str, signature: Option<ModuleSignature>) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        self.check_unsealed(name)?;
+
+        metadata.signature = signature;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated signature for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Get modules approved for a code hash other than their current signature's, sorted
+    ///
+    /// The approved-at hash is recorded by `update_review_status`. A module approved while
+    /// unsigned becomes stale once it is signed, and vice versa.
+    pub fn stale_approvals(&self) -> Vec<String> {
+        let mut stale: Vec<String> = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| {
+                let metadata = &entry.metadata;
+                matches!(metadata.review_status, CodeReviewStatus::Approved { .. })
+                    && metadata.approved_code_hash.as_deref()
+                        != metadata.signature.as_ref().map(|s| s.code_hash.as_str())
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        stale.sort_unstable();
+        stale
+    }
+
+    /// Update module permissions
+    pub fn update_permissions(&self, name: &str, permissions: ModulePermissions) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        self.check_unsealed(name)?;
+
+        metadata.permissions = permissions;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated permissions for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Update module sandbox configuration
+    pub fn update_sandbox_config(&self, name: &str, sandbox_config: SandboxConfig) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+        self.check_unsealed(name)?;
+
+        metadata.sandbox_config = sandbox_config;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated sandbox config for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Replace the factory of a registered module
+    ///
+    /// Metadata and creation stats are kept. A singleton that was already
+    /// created keeps its existing instance. A context factory is replaced too.
+    pub fn replace_factory(&self, name: &str, factory: ModuleFactory) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let entry = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        entry.factory = factory;
+        entry.context_factory = None;
+        info!("Replaced factory for module: {}", name);
+        Ok(())
+    }
+
+    /// Replace a module's factory until the returned guard is dropped
+    ///
+    /// Dropping the guard restores the original factory. If no module with this name
+    /// existed, a temporary module of type `"override"` is inserted without registration
+    /// checks and removed again on drop. A module registered under the name while the
+    /// guard is alive replaces the override and is kept on drop; a renamed module is
+    /// restored under its new name.
+    pub fn override_scope(&self, name: &str, factory: ModuleFactory) -> FactoryOverrideGuard<'_> {
+        let key = self.key(name).into_owned();
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let (registration_index, original) = match modules.get_mut(&key) {
+            Some(entry) => (
+                entry.registration_index,
+                Some((
+                    std::mem::replace(&mut entry.factory, factory),
+                    entry.context_factory.take(),
+                )),
+            ),
+            None => {
+                let metadata = ModuleMetadata::new(
+                    name.to_string(),
+                    "override".to_string(),
+                    "factory".to_string(),
+                    module_path!().to_string(),
+                    "Module".to_string(),
+                );
+                let entry = ModuleEntry::new(metadata, factory);
+                let registration_index = entry.registration_index;
+                modules.insert(key.clone(), entry);
+                (registration_index, None)
+            }
+        };
+        drop(modules);
+
+        info!("Overriding factory for module: {}", name);
+        FactoryOverrideGuard {
+            registry: self,
+            key,
+            registration_index,
+            original,
+        }
+    }
+
+    /// Limit a module to `max` creations per `window`
+    ///
+    /// Creations beyond the limit fail with `RegistryError::RateLimited` until tokens refill.
+    pub fn set_rate_limit(&self, name: &str, max: u32, window: Duration) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let entry = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        entry.metadata.rate_limit = Some((max, window));
+        entry.metadata.touch_at(self.timestamp());
+        *entry
+            .rate_bucket
+            .lock()
+            .expect("Failed to acquire rate limit lock") = None;
+
+        drop(modules);
+
+        info!("Set rate limit for module: {} ({} per {:?})", name, max, window);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Replace the clock used for time-dependent behavior such as rate limiting
+    ///
+    /// Also stamps `last_modified` and `last_created`; `register_metadata` keeps the
+    /// `last_modified` it is given.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.write().expect("Failed to acquire write lock") = clock;
+    }
+
+    /// Get the current time from the registry clock
+    pub(crate) fn now(&self) -> SystemTime {
+        self.clock.read().expect("Failed to acquire read lock").now()
+    }
+
+    /// Get the current Unix timestamp in seconds from the registry clock
+    fn timestamp(&self) -> u64 {
+        self.now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// Enable or disable creation of a module
+    ///
+    /// Disabled modules stay registered with their metadata and stats, but
+    /// creation fails with `RegistryError::Disabled`.
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry {
+            metadata, stats, ..
+        } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        if enabled && !metadata.enabled {
+            stats.consecutive_failures.store(0, Ordering::Relaxed);
+        }
+        metadata.enabled = enabled;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Set module {} enabled: {}", name, enabled);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Disable every enabled module matching `pred`
+    ///
+    /// Returns the names of the modules that were disabled, sorted.
+    pub fn disable_where(&self, pred: impl Fn(&ModuleMetadata) -> bool) -> Vec<String> {
+        self.set_enabled_where(pred, false)
+    }
+
+    /// Enable every disabled module matching `pred`
+    ///
+    /// Returns the names of the modules that were enabled, sorted.
+    pub fn enable_where(&self, pred: impl Fn(&ModuleMetadata) -> bool) -> Vec<String> {
+        self.set_enabled_where(pred, true)
+    }
+
+    /// Set `enabled` on every matching module whose state differs, returning their names
+    fn set_enabled_where(
+        &self,
+        pred: impl Fn(&ModuleMetadata) -> bool,
+        enabled: bool,
+    ) -> Vec<String> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let mut changed = Vec::new();
+        for (name, entry) in modules.iter_mut() {
+            if entry.metadata.enabled == enabled || !pred(&entry.metadata) {
+                continue;
+            }
+            if enabled {
+                entry.stats.consecutive_failures.store(0, Ordering::Relaxed);
+            }
+            entry.metadata.enabled = enabled;
+            entry.metadata.touch_at(self.timestamp());
+            changed.push(name.clone());
+        }
+        drop(modules);
+
+        changed.sort_unstable();
+        for name in &changed {
+            info!("Set module {} enabled: {}", name, enabled);
+            self.events.emit(RegistryEvent::Updated { name: name.clone() });
+        }
+        changed
+    }
 
+    /// Set an annotation on a module, replacing any previous value for the key
+    pub fn set_annotation(&self, name: &str, key: &str, value: &str) -> Result<()> {
         let mut modules = self.modules.write().expect("Failed to acquire write lock");
-        modules.insert(name.to_string(), (metadata, factory));
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
 
-        info!("Registered secure module: {} (type: {})", name, module_type);
+        metadata
+            .annotations
+            .insert(key.to_string(), value.to_string());
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Set annotation {} for module: {}", key, name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
     }
 
-    /// Update code review status
-    pub fn update_review_status(
+    /// Get an annotation of a module
+    pub fn get_annotation(&self, name: &str, key: &str) -> Option<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(self.key(name).as_ref())
+            .and_then(|entry| entry.metadata.annotations.get(key).cloned())
+    }
+
+    /// Set the modules that must be initialized before a module
+    pub fn set_dependencies<I, S>(&self, name: &str, dependencies: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        metadata.dependencies = dependencies.into_iter().map(Into::into).collect();
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated dependencies for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Set the modules initialized before a module if they are registered
+    ///
+    /// Unlike hard dependencies, unregistered soft dependencies are skipped.
+    pub fn set_soft_dependencies<I, S>(&self, name: &str, dependencies: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        metadata.soft_dependencies = dependencies.into_iter().map(Into::into).collect();
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated soft dependencies for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Disable a module after `n` consecutive factory failures, or never if `n` is 0
+    ///
+    /// A successful creation resets the count. When the threshold is hit the module is
+    /// disabled and `RegistryEvent::CircuitOpened` is emitted; re-enable it with
+    /// `set_enabled`. Off by default.
+    pub fn set_failure_threshold(&self, n: usize) {
+        self.failure_threshold.store(n, Ordering::Relaxed);
+    }
+
+    /// Set the maximum dependency chain depth, `DEFAULT_MAX_DEPENDENCY_DEPTH` by default
+    pub fn set_max_dependency_depth(&self, depth: usize) {
+        self.max_dependency_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Set the module types a module is initialized before and after
+    ///
+    /// Every module of a type in `before_types` is initialized after this module, and every
+    /// module of a type in `after_types` before it. Fails with
+    /// `RegistryError::OrderingContradiction` if either list contains the module's own type.
+    pub fn set_type_ordering(
         &self,
         name: &str,
-        status: CodeReviewStatus,
+        before_types: Vec<String>,
+        after_types: Vec<String>,
     ) -> Result<()> {
         let mut modules = self.modules.write().expect("Failed to acquire write lock");
-        let (metadata, factory) = modules
-            .get_mut(name)
-            .ok_or_else(|| anyhow::anyhow!("Module not found: {}", name))?;
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
 
-        metadata.review_status = status;
-        info!("Updated review status for module: {}", name);
+        if before_types.contains(&metadata.module_type)
+            || after_types.contains(&metadata.module_type)
+        {
+            return Err(RegistryError::OrderingContradiction {
+                name: name.to_string(),
+                module_type: metadata.module_type.clone(),
+            }
+            .into());
+        }
+
+        metadata.before_types = before_types;
+        metadata.after_types = after_types;
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Updated type ordering for module: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Get all modules ordered so that every module comes after its dependencies
+    ///
+    /// Registered soft dependencies are ordered like hard ones, and `before_types` and
+    /// `after_types` order a module relative to every module of those types. Fails on
+    /// missing hard dependencies, cycles, chains deeper than the maximum dependency depth,
+    /// and modules ordered relative to their own type. Independent modules are ordered by name.
+    pub fn initialization_order(&self) -> Result<Vec<String>> {
+        let modules = self.modules.read().expect("Failed to acquire read lock");
+        let mut roots: Vec<&str> = modules.keys().map(String::as_str).collect();
+        roots.sort_unstable();
+
+        let mut by_type: HashMap<&str, Vec<&str>> = HashMap::new();
+        for &key in &roots {
+            by_type
+                .entry(modules[key].metadata.module_type.as_str())
+                .or_default()
+                .push(key);
+        }
+
+        // Extra edges from type ordering: module key -> keys initialized before it
+        let mut ordered_after: HashMap<&str, Vec<&str>> = HashMap::new();
+        for &key in &roots {
+            let metadata = &modules[key].metadata;
+            for module_type in metadata.before_types.iter().chain(&metadata.after_types) {
+                if *module_type == metadata.module_type {
+                    return Err(RegistryError::OrderingContradiction {
+                        name: key.to_string(),
+                        module_type: module_type.clone(),
+                    }
+                    .into());
+                }
+            }
+
+            for module_type in &metadata.before_types {
+                for &later in by_type.get(module_type.as_str()).into_iter().flatten() {
+                    ordered_after.entry(later).or_default().push(key);
+                }
+            }
+            for module_type in &metadata.after_types {
+                ordered_after
+                    .entry(key)
+                    .or_default()
+                    .extend(by_type.get(module_type.as_str()).into_iter().flatten());
+            }
+        }
+
+        Ok(self.dependency_order(&modules, &ordered_after, roots)?)
+    }
+
+    /// Finalize live instances in reverse `initialization_order`
+    ///
+    /// Every instance is finalized even if an earlier finalizer fails; results are
+    /// returned in the order the finalizers ran. Instances of unregistered modules come
+    /// last and fail with a not-found error. Fails without finalizing anything if the
+    /// initialization order cannot be computed.
+    pub fn finalize_all_in_reverse_init_order(
+        &self,
+        instances: &[(String, Box<dyn Any + Send + Sync>)],
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let position: HashMap<String, usize> = self
+            .initialization_order()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+
+        let mut ordered: Vec<&(String, Box<dyn Any + Send + Sync>)> = instances.iter().collect();
+        ordered.sort_by_key(|(name, _)| std::cmp::Reverse(position.get(self.key(name).as_ref())));
+
+        Ok(ordered
+            .into_iter()
+            .map(|(name, instance)| (name.clone(), self.finalize(name, &**instance)))
+            .collect())
+    }
+
+    /// Order `roots` and their transitive dependencies, dependencies first
+    ///
+    /// `ordered_after` adds edges by registry key on top of the named dependencies.
+    /// Walks the graph with an explicit stack so deep chains cannot overflow the call stack.
+    /// A module's depth is the number of modules in its longest dependency chain, itself included.
+    fn dependency_order<'a>(
+        &self,
+        modules: &'a HashMap<String, ModuleEntry>,
+        ordered_after: &HashMap<&'a str, Vec<&'a str>>,
+        roots: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<String>, RegistryError> {
+        let max_depth = self.max_dependency_depth.load(Ordering::Relaxed);
+        let mut order = Vec::new();
+        let mut depths: HashMap<&str, usize> = HashMap::new();
+
+        for root in roots {
+            if depths.contains_key(root) {
+                continue;
+            }
+
+            // Frames of (module key, next dependency index, deepest dependency so far)
+            let mut path: Vec<(&str, usize, usize)> = vec![(root, 0, 0)];
+            while let Some((key, next, deepest)) = path.last_mut() {
+                let key = *key;
+                let metadata = &modules[key].metadata;
+                let hard_count = metadata.dependencies.len();
+                let named_count = hard_count + metadata.soft_dependencies.len();
+                let dependency = if *next < hard_count {
+                    metadata.dependencies.get(*next).map(Edge::Hard)
+                } else if *next < named_count {
+                    metadata
+                        .soft_dependencies
+                        .get(*next - hard_count)
+                        .map(Edge::Soft)
+                } else {
+                    ordered_after
+                        .get(key)
+                        .and_then(|keys| keys.get(*next - named_count))
+                        .map(|&dep_key| Edge::Key(dep_key))
+                };
+                let Some(dependency) = dependency else {
+                    let depth = *deepest + 1;
+                    if depth > max_depth {
+                        return Err(RegistryError::DependencyTooDeep { depth });
+                    }
+
+                    path.pop();
+                    if let Some((_, _, parent_deepest)) = path.last_mut() {
+                        *parent_deepest = (*parent_deepest).max(depth);
+                    }
+                    depths.insert(key, depth);
+                    order.push(key.to_string());
+                    continue;
+                };
+                *next += 1;
+
+                let dep_key = match dependency {
+                    Edge::Key(dep_key) => dep_key,
+                    Edge::Hard(name) | Edge::Soft(name) => {
+                        match modules.get_key_value(self.key(name).as_ref()) {
+                            Some((dep_key, _)) => dep_key.as_str(),
+                            None if matches!(dependency, Edge::Soft(_)) => continue,
+                            None => {
+                                return Err(RegistryError::MissingDependency {
+                                    name: key.to_string(),
+                                    dependency: name.clone(),
+                                })
+                            }
+                        }
+                    }
+                };
+
+                if let Some(&dep_depth) = depths.get(dep_key) {
+                    *deepest = (*deepest).max(dep_depth);
+                    continue;
+                }
+                if path.iter().any(|(k, _, _)| *k == dep_key) {
+                    return Err(RegistryError::DependencyCycle(dep_key.to_string()));
+                }
+                if path.len() >= max_depth {
+                    return Err(RegistryError::DependencyTooDeep {
+                        depth: path.len() + 1,
+                    });
+                }
+
+                path.push((dep_key, 0, 0));
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Mark a module as deprecated with a reason or replacement note
+    ///
+    /// Deprecated modules can still be created but log a warning.
+    pub fn mark_deprecated(&self, name: &str, note: &str) -> Result<()> {
+        let mut modules = self.modules.write().expect("Failed to acquire write lock");
+        let ModuleEntry { metadata, .. } = modules
+            .get_mut(self.key(name).as_ref())
+            .ok_or_else(|| module_not_found(name))?;
+
+        metadata.deprecated = Some(note.to_string());
+        metadata.touch_at(self.timestamp());
+        drop(modules);
+
+        info!("Marked module as deprecated: {}", name);
+        self.events.emit(RegistryEvent::Updated {
+            name: name.to_string(),
+        });
         Ok(())
     }
 
+    /// Get all deprecated modules with their deprecation notes
+    pub fn deprecated_modules(&self) -> Vec<(String, String)> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter_map(|(name, entry)| {
+                entry
+                    .metadata
+                    .deprecated
+                    .as_ref()
+                    .map(|note| (name.clone(), note.clone()))
+            })
+            .collect()
+    }
+
+    /// Get names of modules registered or updated at or after the given Unix timestamp
+    pub fn modified_since(&self, unix_ts: u64) -> Vec<String> {
+        self.modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter(|(_, entry)| entry.metadata.last_modified >= unix_ts)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Get modules whose signature expires within `window_secs`, with seconds until expiry
+    ///
+    /// Uses the registry clock. Unsigned modules and already expired signatures are
+    /// excluded. Results are ordered by time until expiry, soonest first.
+    pub fn signatures_expiring_within(&self, window_secs: u64) -> Vec<(String, u64)> {
+        let now = self.timestamp();
+
+        let mut expiring: Vec<(String, u64)> = self
+            .modules
+            .read()
+            .expect("Failed to acquire read lock")
+            .iter()
+            .filter_map(|(name, entry)| {
+                let signature = entry.metadata.signature.as_ref()?;
+                let expires_at = signature.timestamp.saturating_add(SIGNATURE_EXPIRY_SECONDS);
+                let remaining = expires_at.checked_sub(now)?;
+                (remaining <= window_secs).then(|| (name.clone(), remaining))
+            })
+            .collect();
+
+        expiring.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        expiring
+    }
+
     /// Get security report for all modules
     pub fn get_security_report(&self) -> HashMap<String, SecurityReport> {
         let modules = self.modules.read().expect("Failed to acquire read lock");
         let mut report = HashMap::new();
 
-        for (name, (metadata, _)) in modules.iter() {
-            let security_report = SecurityReport {
-                name: name.clone(),
-                has_signature: metadata.signature.is_some(),
-                signature_verified: metadata.signature.is_some(),
-                is_approved: matches!(metadata.review_status, CodeReviewStatus::Approved { .. }),
-                has_supply_chain: metadata.supply_chain.is_some(),
-                supply_chain_verified: metadata.supply_chain.is_some(),
-                permissions: metadata.permissions.clone(),
-                sandbox_enabled: metadata.sandbox_config.enabled,
-            };
-            report.insert(name.clone(), security_report);
+        for (name, ModuleEntry { metadata, .. }) in modules.iter() {
+            report.insert(name.clone(), security_report_of(name, metadata));
         }
 
         report
     }
 
+    /// Export the security report of every module as CSV, sorted by module name
+    ///
+    /// Columns: module, has_signature, signature_verified, is_approved, has_supply_chain,
+    /// sandbox_enabled, risk_level. Fields containing commas, quotes or line breaks are
+    /// quoted, with inner quotes doubled.
+    pub fn security_report_csv(&self) -> String {
+        let mut rows: Vec<(SecurityReport, SecurityRiskLevel)> = {
+            let modules = self.modules.read().expect("Failed to acquire read lock");
+            modules
+                .iter()
+                .map(|(name, ModuleEntry { metadata, .. })| {
+                    let check = SecurityValidator::comprehensive_check(metadata);
+                    (security_report_of(name, metadata), check.risk_level)
+                })
+                .collect()
+        };
+        rows.sort_unstable_by(|a, b| a.0.name.cmp(&b.0.name));
+
+        let mut csv = String::from(
+            "module,has_signature,signature_verified,is_approved,has_supply_chain,\
+             sandbox_enabled,risk_level\n",
+        );
+        for (report, risk_level) in rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{:?}\n",
+                csv_field(&report.name),
+                report.has_signature,
+                report.signature_verified,
+                report.is_approved,
+                report.has_supply_chain,
+                report.sandbox_enabled,
+                risk_level
+            ));
+        }
+        csv
+    }
+
     /// Perform comprehensive security check on all modules
     pub fn security_audit(&self) -> HashMap<String, SecurityCheckResult> {
         let modules = self.modules.read().expect("Failed to acquire read lock");
         let mut audit_results = HashMap::new();
 
-        for (name, (metadata, _)) in modules.iter() {
+        for (name, ModuleEntry { metadata, .. }) in modules.iter() {
             let security_check = SecurityValidator::comprehensive_check(metadata);
             audit_results.insert(name.clone(), security_check);
         }
 
         audit_results
     }
+
+    /// Get all modules with their risk level, from highest to lowest risk
+    ///
+    /// Modules with the same risk level are ordered by name.
+    pub fn modules_by_risk(&self) -> Vec<(String, SecurityRiskLevel)> {
+        let mut ranked: Vec<(String, SecurityRiskLevel)> = self
+            .security_audit()
+            .into_iter()
+            .map(|(name, result)| (name, result.risk_level))
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Log the security audit as one structured event per module
+    ///
+    /// Emits `module`, `is_secure`, `risk_level`, `issue_count` and `warning_count` fields.
+    /// No-op without the `tracing` feature.
+    pub fn log_security_audit(&self) {
+        #[cfg(feature = "tracing")]
+        for (name, result) in self.security_audit() {
+            info!(
+                module = %name,
+                is_secure = result.is_secure,
+                risk_level = ?result.risk_level,
+                issue_count = result.issues.len(),
+                warning_count = result.warnings.len(),
+                "Security audit result"
+            );
+        }
+    }
+
+    /// Log each security issue of a module at a level matching its severity
+    ///
+    /// Critical issues log at `ERROR`, High at `WARN`, Medium at `INFO` and Low at `DEBUG`.
+    /// Unknown modules log nothing. No-op without the `tracing` feature.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn log_issues(&self, name: &str) {
+        #[cfg(feature = "tracing")]
+        if let Some(metadata) = self.get_metadata(name) {
+            for issue in SecurityValidator::comprehensive_check(&metadata).issues {
+                log_issue(name, &issue);
+            }
+        }
+    }
+}
+
+/// Log a module's security issue at the level matching its severity
+#[cfg(feature = "tracing")]
+fn log_issue(name: &str, issue: &SecurityIssue) {
+    match issue.severity {
+        SecuritySeverity::Critical => {
+            error!(module = %name, component = %issue.component, "{}", issue.message)
+        }
+        SecuritySeverity::High => {
+            warn!(module = %name, component = %issue.component, "{}", issue.message)
+        }
+        SecuritySeverity::Medium => {
+            info!(module = %name, component = %issue.component, "{}", issue.message)
+        }
+        SecuritySeverity::Low => {
+            debug!(module = %name, component = %issue.component, "{}", issue.message)
+        }
+    }
+}
+
+/// Borrow the trait object inside a module created by `create_any`
+///
+/// Factories follow the double-box convention: they return
+/// `Box::new(Box::new(module) as Box<dyn Trait>)`, so the `Any` holds a
+/// `Box<dyn Trait>`. Use `downcast_module::<dyn Trait>(&*any)` to borrow it.
+#[allow(clippy::borrowed_box)]
+pub fn downcast_module<T: ?Sized + 'static>(any: &(dyn Any + Send + Sync)) -> Option<&Box<T>> {
+    any.downcast_ref::<Box<T>>()
+}
+
+/// Build the security report fields of one module
+fn security_report_of(name: &str, metadata: &ModuleMetadata) -> SecurityReport {
+    SecurityReport {
+        name: name.to_string(),
+        has_signature: metadata.signature.is_some(),
+        signature_verified: metadata.signature.is_some(),
+        is_approved: matches!(metadata.review_status, CodeReviewStatus::Approved { .. }),
+        has_supply_chain: metadata.supply_chain.is_some(),
+        supply_chain_verified: metadata.supply_chain.is_some(),
+        permissions: metadata.permissions.clone(),
+        sandbox_enabled: metadata.sandbox_config.enabled,
+    }
+}
+
+/// Explain a failed `downcast::<T>` caused by a missing or extra outer box
+///
+/// Only `Any::is` probes decide which hint applies; `type_name` output is not stable
+/// and only appears in the message text.
+fn double_box_hint<T: 'static>(instance: &(dyn Any + Send + Sync)) -> String {
+    let requested = std::any::type_name::<T>();
+
+    if instance.is::<Box<T>>() {
+        return format!(
+            "The factory double-boxes; did you mean to downcast to Box<{}>?",
+            requested
+        );
+    }
+    if instance.is::<Box<dyn Module>>() {
+        return "The factory double-boxes as Box<dyn Module>; did you mean to downcast to \
+                Box<dyn Module> (see `downcast_module`)?"
+            .to_string();
+    }
+    format!(
+        "The module holds neither {0} nor Box<{0}>. Factories for trait objects must \
+         double-box with Box::new(Box::new(module) as Box<dyn Trait>); other modules are \
+         downcast to the concrete type the factory returns",
+        requested
+    )
+}
+
+/// Get the names that occur more than once among `registrations`, sorted
+#[cfg(feature = "inventory")]
+pub fn duplicate_registration_names<'a>(
+    registrations: impl IntoIterator<Item = &'a ModuleRegistration>,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut duplicates: Vec<String> = registrations
+        .into_iter()
+        .filter(|reg| !seen.insert(reg.name))
+        .map(|reg| reg.name.to_string())
+        .collect();
+
+    duplicates.sort_unstable();
+    duplicates.dedup();
+    duplicates
+}
+
+/// Shorten a module name to at most `max` characters for display, marking the cut
+///
+/// Truncates on character boundaries, so multibyte names never split mid-character.
+/// The result, including `TRUNCATION_MARKER`, is at most `max` characters long; the
+/// marker is left out when `max` is too small to hold it.
+pub fn truncate_name_for_display(name: &str, max: usize) -> String {
+    if name.chars().count() <= max {
+        return name.to_string();
+    }
+
+    let marker = TRUNCATION_MARKER.chars().count();
+    let (keep, marker) = if max > marker {
+        (max - marker, TRUNCATION_MARKER)
+    } else {
+        (max, "")
+    };
+    let end = name
+        .char_indices()
+        .nth(keep)
+        .map_or(name.len(), |(index, _)| index);
+
+    format!("{}{}", &name[..end], marker)
+}
+
+/// Build a plain not-found error for a module
+/// Metadata for a module registered through one of the registry's own register paths
+fn registry_metadata(name: &str, module_type: &str, instantiate_fn: &str) -> ModuleMetadata {
+    ModuleMetadata::new(
+        name.to_string(),
+        module_type.to_string(),
+        instantiate_fn.to_string(),
+        module_path!().to_string(),
+        "Module".to_string(),
+    )
+}
+
+fn module_not_found(name: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Module not found: {}",
+        truncate_name_for_display(name, MAX_DISPLAY_NAME_LENGTH)
+    )
+}
+
+/// Set a module's review status, recording the approved code hash
+fn set_review_status(metadata: &mut ModuleMetadata, status: CodeReviewStatus, timestamp: u64) {
+    metadata.approved_code_hash = match status {
+        CodeReviewStatus::Approved { .. } => metadata
+            .signature
+            .as_ref()
+            .map(|signature| signature.code_hash.clone()),
+        _ => None,
+    };
+    metadata.review_status = status;
+    metadata.touch_at(timestamp);
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Context factory instantiating the WASM module bound to the module being created
+#[cfg(feature = "wasm")]
+fn wasm_factory(ctx: &RegistryContext<'_>) -> Result<Box<dyn Any + Send + Sync>> {
+    let (factory, permissions) = {
+        let modules = ctx.registry.modules.read().expect("Failed to acquire read lock");
+        let entry = modules
+            .get(ctx.key)
+            .ok_or_else(|| module_not_found(ctx.name))?;
+        let factory = entry
+            .wasm
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No WASM module is bound to: {}", ctx.name))?;
+        (factory, entry.metadata.permissions.clone())
+    };
+
+    factory.instantiate(&permissions)
+}
+
+/// Placeholder factory for modules loaded without one
+fn unresolved_factory() -> Result<Box<dyn Any + Send + Sync>> {
+    Err(anyhow::anyhow!(
+        "No factory is bound to this module; bind one with replace_factory"
+    ))
+}
+
+/// Build a not-found error listing the registered names closest to `name`
+///
+/// Candidates are ranked by the length of their common prefix with `name`, then by name.
+fn not_found_with_candidates(modules: &HashMap<String, ModuleEntry>, name: &str) -> anyhow::Error {
+    if modules.is_empty() {
+        return anyhow::anyhow!(
+            "Module not found: {}. No modules are registered",
+            truncate_name_for_display(name, MAX_DISPLAY_NAME_LENGTH)
+        );
+    }
+
+    let shared_prefix = |candidate: &str| {
+        candidate
+            .chars()
+            .zip(name.chars())
+            .take_while(|(a, b)| a == b)
+            .count()
+    };
+
+    let mut candidates: Vec<&str> = modules.values().map(|e| e.metadata.name.as_str()).collect();
+    candidates.sort_by(|a, b| {
+        shared_prefix(b)
+            .cmp(&shared_prefix(a))
+            .then_with(|| a.cmp(b))
+    });
+
+    let more = if candidates.len() > MAX_NOT_FOUND_CANDIDATES {
+        ", ..."
+    } else {
+        ""
+    };
+    candidates.truncate(MAX_NOT_FOUND_CANDIDATES);
+
+    anyhow::anyhow!(
+        "Module not found: {}. Available: {}{}",
+        truncate_name_for_display(name, MAX_DISPLAY_NAME_LENGTH),
+        candidates.join(", "),
+        more
+    )
+}
+
+/// Validate a module name for registration, returning it trimmed
+fn validated_name(name: &str) -> Result<&str, RegistryError> {
+    trimmed_name(name).ok_or_else(|| RegistryError::InvalidName(name.to_string()))
 }
 
 impl Default for ModuleRegistry {
@@ -338,3 +3525,255 @@ impl Default for ModuleRegistry {
         Self::new()
     }
 }
+
+/// Builder configuring a `ModuleRegistry` in one place
+///
+/// Each setter matches a `ModuleRegistry` configuration method; unset options keep
+/// the defaults of `ModuleRegistry::new()`.
+#[derive(Default)]
+pub struct ModuleRegistryBuilder {
+    registry: ModuleRegistry,
+}
+
+impl ModuleRegistryBuilder {
+    /// Create a builder with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat module names case-insensitively by lowercasing registry keys
+    pub fn case_insensitive(self) -> Self {
+        self.key_normalizer(Arc::new(|name: &str| name.to_lowercase()))
+    }
+
+    /// Set a function normalizing module names into registry keys
+    pub fn key_normalizer(self, normalizer: KeyNormalizer) -> Self {
+        self.registry.set_key_normalizer(normalizer);
+        self
+    }
+
+    /// Restrict registration to the given module types
+    pub fn allowed_types<I, S>(self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.registry.set_allowed_types(types);
+        self
+    }
+
+    /// Verify secure registrations against a policy
+    pub fn verify_on_register(mut self, policy: SecurityPolicy) -> Self {
+        self.registry.verify_policy = Some(policy);
+        self
+    }
+
+    /// Set a decorator applied to metadata before insertion
+    pub fn metadata_decorator(self, decorator: MetadataDecorator) -> Self {
+        self.registry.set_metadata_decorator(decorator);
+        self
+    }
+
+    /// Set an interceptor wrapping every factory invocation
+    pub fn factory_interceptor(self, interceptor: FactoryInterceptor) -> Self {
+        self.registry.set_factory_interceptor(interceptor);
+        self
+    }
+
+    /// Set the time source
+    pub fn clock(self, clock: Arc<dyn Clock>) -> Self {
+        self.registry.set_clock(clock);
+        self
+    }
+
+    /// Truncate over-long module paths instead of rejecting them
+    pub fn truncate_paths(self, truncate: bool) -> Self {
+        self.registry.set_truncate_paths(truncate);
+        self
+    }
+
+    /// Refuse to create deprecated modules
+    pub fn deny_deprecated(self, deny: bool) -> Self {
+        self.registry.set_deny_deprecated(deny);
+        self
+    }
+
+    /// Restrict module types to ASCII identifiers
+    pub fn strict_type_charset(self, strict: bool) -> Self {
+        self.registry.set_strict_type_charset(strict);
+        self
+    }
+
+    /// Disable modules after `n` consecutive factory failures
+    pub fn failure_threshold(self, n: usize) -> Self {
+        self.registry.set_failure_threshold(n);
+        self
+    }
+
+    /// Set the maximum dependency chain depth
+    pub fn max_dependency_depth(self, depth: usize) -> Self {
+        self.registry.set_max_dependency_depth(depth);
+        self
+    }
+
+    /// Set the sandbox configuration seeded into modules of a type
+    pub fn type_sandbox_default(self, module_type: &str, cfg: SandboxConfig) -> Self {
+        self.registry.set_type_sandbox_default(module_type, cfg);
+        self
+    }
+
+    /// Build the configured registry
+    pub fn build(self) -> ModuleRegistry {
+        self.registry
+    }
+}
+
+/// Module instance created by `ModuleRegistry::create_guarded`
+///
+/// Derefs to the created instance and frees its live instance slot when dropped.
+pub struct GuardedInstance {
+    instance: Box<dyn Any + Send + Sync>,
+    live: Arc<AtomicUsize>,
+}
+
+impl std::ops::Deref for GuardedInstance {
+    type Target = dyn Any + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.instance
+    }
+}
+
+impl std::ops::DerefMut for GuardedInstance {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.instance
+    }
+}
+
+impl Drop for GuardedInstance {
+    fn drop(&mut self) {
+        self.live.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Factory that receives the registry and host data when creating a module
+pub type ContextFactory = fn(&RegistryContext<'_>) -> Result<Box<dyn Any + Send + Sync>>;
+
+/// Context passed to a `ContextFactory`
+pub struct RegistryContext<'a> {
+    registry: &'a ModuleRegistry,
+    name: &'a str,
+    key: &'a str,
+    host: Option<&'a (dyn Any + Send + Sync)>,
+}
+
+impl<'a> RegistryContext<'a> {
+    /// Registry creating the module, for resolving dependencies
+    pub fn registry(&self) -> &'a ModuleRegistry {
+        self.registry
+    }
+
+    /// Name the module is being created under
+    pub fn module_name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Registry key the module is stored under, after normalization and aliases
+    pub fn module_key(&self) -> &'a str {
+        self.key
+    }
+
+    /// Host data passed to `create_with_context`, if it has type `T`
+    pub fn host<T: 'static>(&self) -> Option<&'a T> {
+        self.host?.downcast_ref::<T>()
+    }
+}
+
+/// Guard returned by `ModuleRegistry::override_scope`
+///
+/// Restores the original factory, or removes the temporary module, when dropped, even if
+/// the module was renamed meanwhile. A module registered again under the same name while
+/// the guard is alive is left alone.
+pub struct FactoryOverrideGuard<'a> {
+    registry: &'a ModuleRegistry,
+    key: String,
+    registration_index: u64,
+    original: Option<(ModuleFactory, Option<ContextFactory>)>,
+}
+
+impl Drop for FactoryOverrideGuard<'_> {
+    fn drop(&mut self) {
+        let mut modules = match self.registry.modules.write() {
+            Ok(modules) => modules,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        // The module may have been renamed since, so look it up by registration index
+        let key = if modules
+            .get(&self.key)
+            .is_some_and(|entry| entry.registration_index == self.registration_index)
+        {
+            Some(self.key.clone())
+        } else {
+            modules
+                .iter()
+                .find(|(_, entry)| entry.registration_index == self.registration_index)
+                .map(|(key, _)| key.clone())
+        };
+        let Some(key) = key else {
+            return;
+        };
+
+        match self.original {
+            Some((factory, context_factory)) => {
+                let entry = modules.get_mut(&key).expect("Module found above");
+                entry.factory = factory;
+                entry.context_factory = context_factory;
+            }
+            None => {
+                modules.remove(&key);
+            }
+        }
+        drop(modules);
+
+        info!("Restored factory for module: {}", key);
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+
+    fn factory() -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(0u32))
+    }
+
+    #[test]
+    #[traced_test]
+    fn log_security_audit_reports_insecure_modules() {
+        let registry = ModuleRegistry::new();
+        registry.register("unsigned", "plugin", factory).unwrap();
+
+        registry.log_security_audit();
+
+        assert!(logs_contain("module=unsigned"));
+        assert!(logs_contain("is_secure=false"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn critical_issues_log_at_error() {
+        log_issue(
+            "compromised",
+            &SecurityIssue {
+                severity: SecuritySeverity::Critical,
+                message: "Known malicious module".to_string(),
+                component: "supply_chain".to_string(),
+            },
+        );
+
+        assert!(logs_contain("ERROR"));
+        assert!(logs_contain("Known malicious module"));
+    }
+}
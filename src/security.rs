@@ -1,6 +1,7 @@
 //! Security-related functionality for module registry
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::constants::*;
@@ -12,6 +13,14 @@ pub struct SecurityValidator;
 impl SecurityValidator {
     /// Verify module signature
     pub fn verify_signature(metadata: &ModuleMetadata) -> Result<bool> {
+        Self::verify_signature_with(metadata, &[DEFAULT_SIGNATURE_ALGORITHM.to_string()])
+    }
+
+    /// Verify module signature, accepting any of the given algorithms
+    fn verify_signature_with(
+        metadata: &ModuleMetadata,
+        allowed_algorithms: &[String],
+    ) -> Result<bool> {
         match &metadata.signature {
             Some(sig) => {
                 // Check if signature is not expired
@@ -25,7 +34,7 @@ impl SecurityValidator {
                 }
 
                 // Verify signature algorithm
-                if sig.algorithm != DEFAULT_SIGNATURE_ALGORITHM {
+                if !allowed_algorithms.contains(&sig.algorithm) {
                     return Ok(false);
                 }
 
@@ -85,13 +94,37 @@ impl SecurityValidator {
         }
     }
 
+    /// Verify that the signed code hash matches the supply chain commit
+    ///
+    /// Requires a signature, supply chain info, and an expected code hash for the commit.
+    pub fn verify_provenance(metadata: &ModuleMetadata) -> Result<bool> {
+        match (&metadata.signature, &metadata.supply_chain) {
+            (Some(sig), Some(chain)) => Ok(chain
+                .expected_code_hash
+                .as_ref()
+                .is_some_and(|expected| *expected == sig.code_hash)),
+            _ => Ok(false), // Provenance needs both signature and supply chain
+        }
+    }
+
     /// Perform comprehensive security check
     pub fn comprehensive_check(metadata: &ModuleMetadata) -> SecurityCheckResult {
+        Self::comprehensive_check_with_policy(metadata, &SecurityPolicy::default())
+    }
+
+    /// Perform comprehensive security check using the policy's signature algorithms
+    ///
+    /// A signature using an allowed algorithm other than the policy's preferred one
+    /// adds a Medium issue with component `signature_algorithm`.
+    pub fn comprehensive_check_with_policy(
+        metadata: &ModuleMetadata,
+        policy: &SecurityPolicy,
+    ) -> SecurityCheckResult {
         let mut issues = Vec::new();
         let mut warnings = Vec::new();
 
         // Check signature
-        match Self::verify_signature(metadata) {
+        match Self::verify_signature_with(metadata, &policy.allowed_algorithms) {
             Ok(true) => {
                 // Signature is valid
             }
@@ -110,6 +143,20 @@ impl SecurityValidator {
             }
         }
 
+        // Check for a downgrade to a non-preferred algorithm
+        if let (Some(sig), Some(preferred)) = (&metadata.signature, &policy.preferred_algorithm) {
+            if sig.algorithm != *preferred && policy.allowed_algorithms.contains(&sig.algorithm) {
+                issues.push(SecurityIssue {
+                    severity: SecuritySeverity::Medium,
+                    message: format!(
+                        "Signature uses {} instead of preferred algorithm {}",
+                        sig.algorithm, preferred
+                    ),
+                    component: "signature_algorithm".to_string(),
+                });
+            }
+        }
+
         // Check approval status
         match Self::is_approved(metadata) {
             Ok(true) => {
@@ -150,6 +197,22 @@ impl SecurityValidator {
             }
         }
 
+        // Check provenance when both sides make a claim
+        if let (Some(sig), Some(chain)) = (&metadata.signature, &metadata.supply_chain) {
+            if let Some(expected) = &chain.expected_code_hash {
+                if *expected != sig.code_hash {
+                    issues.push(SecurityIssue {
+                        severity: SecuritySeverity::High,
+                        message: format!(
+                            "Signed code hash does not match supply chain commit {}",
+                            chain.commit_hash
+                        ),
+                        component: "provenance".to_string(),
+                    });
+                }
+            }
+        }
+
         // Check permissions
         if metadata.permissions.system_access && !metadata.sandbox_config.enabled {
             issues.push(SecurityIssue {
@@ -158,6 +221,7 @@ impl SecurityValidator {
                 component: "permissions".to_string(),
             });
         }
+        warnings.extend(metadata.validate_consistency());
 
         let is_secure = issues.is_empty();
         let risk_level = Self::calculate_risk_level(&issues);
@@ -190,8 +254,69 @@ impl SecurityValidator {
     }
 }
 
-/// Security check result
+/// Policy deciding which security checks a module must pass
+///
+/// Issues from checks that are not required are tolerated; any other issue
+/// (e.g. unsandboxed system access) always blocks.
 #[derive(Debug, Clone)]
+pub struct SecurityPolicy {
+    /// Require a valid signature
+    pub require_signature: bool,
+    /// Require code review approval
+    pub require_approval: bool,
+    /// Require verified supply chain info
+    pub require_supply_chain: bool,
+    /// Signature algorithms accepted as valid
+    pub allowed_algorithms: Vec<String>,
+    /// Algorithm signatures should use; other allowed algorithms are reported as downgrades
+    pub preferred_algorithm: Option<String>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self {
+            require_signature: true,
+            require_approval: true,
+            require_supply_chain: true,
+            allowed_algorithms: vec![DEFAULT_SIGNATURE_ALGORITHM.to_string()],
+            preferred_algorithm: None,
+        }
+    }
+}
+
+impl SecurityPolicy {
+    /// Create a policy that only blocks on issues not tied to an optional check
+    pub fn permissive() -> Self {
+        Self {
+            require_signature: false,
+            require_approval: false,
+            require_supply_chain: false,
+            ..Self::default()
+        }
+    }
+
+    /// Get the issues in a check result that this policy does not tolerate
+    pub fn blocking_issues<'a>(&self, result: &'a SecurityCheckResult) -> Vec<&'a SecurityIssue> {
+        result
+            .issues
+            .iter()
+            .filter(|issue| match issue.component.as_str() {
+                "signature" | "signature_algorithm" => self.require_signature,
+                "review" => self.require_approval,
+                "supply_chain" => self.require_supply_chain,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Check if a check result satisfies this policy
+    pub fn allows(&self, result: &SecurityCheckResult) -> bool {
+        self.blocking_issues(result).is_empty()
+    }
+}
+
+/// Security check result
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityCheckResult {
     pub is_secure: bool,
     pub risk_level: SecurityRiskLevel,
@@ -201,7 +326,7 @@ pub struct SecurityCheckResult {
 }
 
 /// Security issue severity
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SecuritySeverity {
     Low,
     Medium,
@@ -209,8 +334,8 @@ pub enum SecuritySeverity {
     Critical,
 }
 
-/// Security risk level
-#[derive(Debug, Clone, PartialEq)]
+/// Security risk level, ordered from `None` to `Critical`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SecurityRiskLevel {
     None,
     Low,
@@ -220,7 +345,7 @@ pub enum SecurityRiskLevel {
 }
 
 /// Security issue
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityIssue {
     pub severity: SecuritySeverity,
     pub message: String,
@@ -228,7 +353,7 @@ pub struct SecurityIssue {
 }
 
 /// Security warning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityWarning {
     pub message: String,
     pub component: String,
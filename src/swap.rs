@@ -0,0 +1,128 @@
+//! Copy-on-write lock backed by `arc-swap`
+//!
+//! `SwapLock` mirrors the `RwLock` API used by the registry. Reads load the current
+//! snapshot without blocking; writes clone the value, mutate the clone and publish it
+//! when the guard drops. Writers are serialized by a mutex.
+
+use arc_swap::{ArcSwap, Guard};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard, PoisonError};
+
+/// Lock with wait-free reads and copy-on-write updates
+pub(crate) struct SwapLock<T> {
+    current: ArcSwap<T>,
+    writer: Mutex<()>,
+}
+
+/// Snapshot of the value at the time of `SwapLock::read`
+pub(crate) struct SwapReadGuard<T> {
+    snapshot: Guard<Arc<T>>,
+}
+
+/// Private copy of the value, published when dropped
+pub(crate) struct SwapWriteGuard<'a, T> {
+    lock: &'a SwapLock<T>,
+    value: Option<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T: Clone> SwapLock<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(value),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Load the current snapshot; never blocks and never fails
+    pub(crate) fn read(&self) -> LockResult<SwapReadGuard<T>> {
+        Ok(SwapReadGuard {
+            snapshot: self.current.load(),
+        })
+    }
+
+    /// Take the writer lock and start editing a copy of the current value
+    pub(crate) fn write(&self) -> LockResult<SwapWriteGuard<'_, T>> {
+        let (writer, poisoned) = match self.writer.lock() {
+            Ok(writer) => (writer, false),
+            Err(poisoned) => (poisoned.into_inner(), true),
+        };
+
+        let guard = SwapWriteGuard {
+            lock: self,
+            value: Some(T::clone(&self.current.load())),
+            _writer: writer,
+        };
+
+        if poisoned {
+            Err(PoisonError::new(guard))
+        } else {
+            Ok(guard)
+        }
+    }
+}
+
+impl<T> Deref for SwapReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.snapshot
+    }
+}
+
+impl<T> Deref for SwapWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("Write guard value taken")
+    }
+}
+
+impl<T> DerefMut for SwapWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("Write guard value taken")
+    }
+}
+
+impl<T> Drop for SwapWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.lock.current.store(Arc::new(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn reads_do_not_wait_for_writers() {
+        let lock = SwapLock::new(vec![1]);
+
+        thread::scope(|scope| {
+            let mut writer = lock.write().unwrap();
+            writer.push(2);
+
+            let (tx, rx) = mpsc::channel();
+            let lock = &lock;
+            scope.spawn(move || {
+                let started = Instant::now();
+                let snapshot = lock.read().unwrap().clone();
+                tx.send((snapshot, started.elapsed())).unwrap();
+            });
+
+            let (snapshot, elapsed) = rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("read blocked behind the writer");
+            assert_eq!(snapshot, [1]);
+            assert!(elapsed < Duration::from_secs(1), "read took {elapsed:?}");
+            drop(writer);
+        });
+
+        assert_eq!(*lock.read().unwrap(), [1, 2]);
+    }
+}
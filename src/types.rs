@@ -1,11 +1,16 @@
 //! Type definitions for module registry
 
 use serde::{Deserialize, Serialize};
-use std::any::Any;
-use std::collections::HashMap;
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use anyhow::Result;
 
 use crate::constants::*;
+use crate::security::SecurityWarning;
 
 /// Base trait that all modules must implement
 pub trait Module: Send + Sync {
@@ -16,8 +21,16 @@ pub trait Module: Send + Sync {
     fn module_type(&self) -> &str;
 }
 
+/// Describes the configuration shape a module expects
+///
+/// Lets frontends render a configuration form for any registered module.
+pub trait ConfigSchema {
+    /// Get the JSON schema of the module's configuration
+    fn schema() -> serde_json::Value;
+}
+
 /// Module signature for cryptographic verification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModuleSignature {
     /// SHA-256 hash of the module code
     pub code_hash: String,
@@ -32,7 +45,7 @@ pub struct ModuleSignature {
 }
 
 /// Module permissions for sandboxing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModulePermissions {
     /// Can access filesystem
     pub filesystem_access: bool,
@@ -67,8 +80,138 @@ impl Default for ModulePermissions {
     }
 }
 
+impl ModulePermissions {
+    /// Names of the built-in presets accepted by `preset`
+    pub const PRESETS: &'static [&'static str] = &["none", "readonly", "network-client", "trusted"];
+
+    /// Get a built-in permission preset by name
+    ///
+    /// - `none`: no access, default limits
+    /// - `readonly`: filesystem access only; pair it with a read-only sandbox
+    /// - `network-client`: network access only
+    /// - `trusted`: every access flag and the full CPU
+    pub fn preset(name: &str) -> Option<Self> {
+        let base = Self::default();
+        match name {
+            "none" => Some(base),
+            "readonly" => Some(Self {
+                filesystem_access: true,
+                ..base
+            }),
+            "network-client" => Some(Self {
+                network_access: true,
+                ..base
+            }),
+            "trusted" => Some(Self {
+                filesystem_access: true,
+                network_access: true,
+                process_spawn: true,
+                env_access: true,
+                system_access: true,
+                cpu_limit_percent: 100,
+                ..base
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Capability a module may request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// Filesystem access
+    FilesystemAccess,
+    /// Network calls
+    NetworkAccess,
+    /// Spawning processes
+    ProcessSpawn,
+    /// Environment variable access
+    EnvAccess,
+    /// System resource access
+    SystemAccess,
+}
+
+impl Permission {
+    /// All permission variants
+    pub const ALL: [Permission; 5] = [
+        Permission::FilesystemAccess,
+        Permission::NetworkAccess,
+        Permission::ProcessSpawn,
+        Permission::EnvAccess,
+        Permission::SystemAccess,
+    ];
+
+    /// Get the permission name as used by `ModulePermissions` fields
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::FilesystemAccess => "filesystem_access",
+            Permission::NetworkAccess => "network_access",
+            Permission::ProcessSpawn => "process_spawn",
+            Permission::EnvAccess => "env_access",
+            Permission::SystemAccess => "system_access",
+        }
+    }
+}
+
+impl ModulePermissions {
+    /// Check if the given capability is granted
+    pub fn grants(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::FilesystemAccess => self.filesystem_access,
+            Permission::NetworkAccess => self.network_access,
+            Permission::ProcessSpawn => self.process_spawn,
+            Permission::EnvAccess => self.env_access,
+            Permission::SystemAccess => self.system_access,
+        }
+    }
+
+    /// Intersect two permission sets
+    ///
+    /// Capabilities are granted only if both sides grant them; numeric limits take the minimum.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            filesystem_access: self.filesystem_access && other.filesystem_access,
+            network_access: self.network_access && other.network_access,
+            process_spawn: self.process_spawn && other.process_spawn,
+            env_access: self.env_access && other.env_access,
+            system_access: self.system_access && other.system_access,
+            memory_limit_mb: self.memory_limit_mb.min(other.memory_limit_mb),
+            cpu_limit_percent: self.cpu_limit_percent.min(other.cpu_limit_percent),
+            timeout_seconds: self.timeout_seconds.min(other.timeout_seconds),
+        }
+    }
+
+    /// Union two permission sets
+    ///
+    /// Capabilities are granted if either side grants them; numeric limits take the maximum.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            filesystem_access: self.filesystem_access || other.filesystem_access,
+            network_access: self.network_access || other.network_access,
+            process_spawn: self.process_spawn || other.process_spawn,
+            env_access: self.env_access || other.env_access,
+            system_access: self.system_access || other.system_access,
+            memory_limit_mb: self.memory_limit_mb.max(other.memory_limit_mb),
+            cpu_limit_percent: self.cpu_limit_percent.max(other.cpu_limit_percent),
+            timeout_seconds: self.timeout_seconds.max(other.timeout_seconds),
+        }
+    }
+
+    /// Check if every capability and limit is within the other permission set
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        (!self.filesystem_access || other.filesystem_access)
+            && (!self.network_access || other.network_access)
+            && (!self.process_spawn || other.process_spawn)
+            && (!self.env_access || other.env_access)
+            && (!self.system_access || other.system_access)
+            && self.memory_limit_mb <= other.memory_limit_mb
+            && self.cpu_limit_percent <= other.cpu_limit_percent
+            && self.timeout_seconds <= other.timeout_seconds
+    }
+}
+
 /// Code review status for modules
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CodeReviewStatus {
     /// Not reviewed yet
     Pending,
@@ -80,8 +223,46 @@ pub enum CodeReviewStatus {
     Rejected { reviewer: String, reason: String, timestamp: u64 },
 }
 
+impl CodeReviewStatus {
+    /// Get the status name, ignoring any reviewer payload
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeReviewStatus::Pending => "pending",
+            CodeReviewStatus::InProgress => "in_progress",
+            CodeReviewStatus::Approved { .. } => "approved",
+            CodeReviewStatus::Rejected { .. } => "rejected",
+        }
+    }
+
+    /// Get the reviewer of an approved or rejected module
+    pub fn reviewer(&self) -> Option<&str> {
+        match self {
+            CodeReviewStatus::Approved { reviewer, .. }
+            | CodeReviewStatus::Rejected { reviewer, .. } => Some(reviewer),
+            _ => None,
+        }
+    }
+
+    /// Get the timestamp of the review decision
+    pub fn timestamp(&self) -> Option<u64> {
+        match self {
+            CodeReviewStatus::Approved { timestamp, .. }
+            | CodeReviewStatus::Rejected { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        }
+    }
+
+    /// Get the reason a module was rejected
+    pub fn rejection_reason(&self) -> Option<&str> {
+        match self {
+            CodeReviewStatus::Rejected { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
 /// Supply chain verification data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SupplyChainInfo {
     /// Source repository URL
     pub source_url: String,
@@ -95,10 +276,13 @@ pub struct SupplyChainInfo {
     pub build_environment: String,
     /// Verifier signature
     pub verifier_signature: Option<String>,
+    /// Code hash expected for the build at `commit_hash`
+    #[serde(default)]
+    pub expected_code_hash: Option<String>,
 }
 
 /// Sandbox configuration for module isolation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SandboxConfig {
     /// Enable sandboxing
     pub enabled: bool,
@@ -130,8 +314,103 @@ impl Default for SandboxConfig {
     }
 }
 
+impl SandboxConfig {
+    /// Parse a compact sandbox spec such as `iso:fs,net;ro;deny:/etc,/root`
+    ///
+    /// Sections are separated by `;`:
+    /// - `off` disables sandboxing
+    /// - `iso:<flags>` isolates `fs`, `net` and/or `proc`
+    /// - `ro` makes the filesystem read-only
+    /// - `allow:<paths>` and `deny:<paths>` list comma-separated paths
+    ///
+    /// Anything not listed is off or empty. Paths cannot contain `,` or `;`.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let mut config = Self {
+            enabled: true,
+            filesystem_isolation: false,
+            network_isolation: false,
+            process_isolation: false,
+            read_only_fs: false,
+            allowed_paths: Vec::new(),
+            denied_paths: Vec::new(),
+        };
+
+        for section in spec.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let (token, values) = match section.split_once(':') {
+                Some((token, values)) => (token, Some(values)),
+                None => (section, None),
+            };
+
+            match (token, values) {
+                ("off", None) => config.enabled = false,
+                ("ro", None) => config.read_only_fs = true,
+                ("iso", Some(flags)) => {
+                    for flag in spec_list(section, flags)? {
+                        match flag {
+                            "fs" => config.filesystem_isolation = true,
+                            "net" => config.network_isolation = true,
+                            "proc" => config.process_isolation = true,
+                            _ => anyhow::bail!("Unknown sandbox isolation flag: {:?}", flag),
+                        }
+                    }
+                }
+                ("allow", Some(paths)) => config
+                    .allowed_paths
+                    .extend(spec_list(section, paths)?.map(String::from)),
+                ("deny", Some(paths)) => config
+                    .denied_paths
+                    .extend(spec_list(section, paths)?.map(String::from)),
+                _ => anyhow::bail!("Unknown sandbox spec token: {:?}", section),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Format the configuration as a compact spec accepted by `from_spec`
+    pub fn to_spec(&self) -> String {
+        let mut sections = Vec::new();
+
+        if !self.enabled {
+            sections.push("off".to_string());
+        }
+
+        let flags: Vec<&str> = [
+            (self.filesystem_isolation, "fs"),
+            (self.network_isolation, "net"),
+            (self.process_isolation, "proc"),
+        ]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect();
+        if !flags.is_empty() {
+            sections.push(format!("iso:{}", flags.join(",")));
+        }
+
+        if self.read_only_fs {
+            sections.push("ro".to_string());
+        }
+        if !self.allowed_paths.is_empty() {
+            sections.push(format!("allow:{}", self.allowed_paths.join(",")));
+        }
+        if !self.denied_paths.is_empty() {
+            sections.push(format!("deny:{}", self.denied_paths.join(",")));
+        }
+
+        sections.join(";")
+    }
+}
+
+/// Split a comma-separated spec value, rejecting empty entries
+fn spec_list<'a>(section: &str, values: &'a str) -> Result<impl Iterator<Item = &'a str>> {
+    if values.split(',').any(|value| value.trim().is_empty()) {
+        anyhow::bail!("Empty value in sandbox spec section: {:?}", section);
+    }
+    Ok(values.split(',').map(str::trim))
+}
+
 /// Module metadata for registration with security features
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModuleMetadata {
     pub name: String,
     pub module_type: String,
@@ -148,6 +427,98 @@ pub struct ModuleMetadata {
     pub supply_chain: Option<SupplyChainInfo>,
     /// Security sandbox configuration
     pub sandbox_config: SandboxConfig,
+    /// Unix timestamp of the last registration or update
+    #[serde(default)]
+    pub last_modified: u64,
+    /// Deprecation note (reason or replacement), if deprecated
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    /// Maximum creations allowed per time window
+    #[serde(default)]
+    pub rate_limit: Option<(u32, Duration)>,
+    /// Free-form tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether at most one shared instance may exist
+    #[serde(default)]
+    pub singleton: bool,
+    /// Whether the module may be created
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Names of modules that must be initialized before this one
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Names of modules initialized before this one when registered, skipped otherwise
+    #[serde(default)]
+    pub soft_dependencies: Vec<String>,
+    /// Module types whose modules are all initialized after this one
+    #[serde(default)]
+    pub before_types: Vec<String>,
+    /// Module types whose modules are all initialized before this one
+    #[serde(default)]
+    pub after_types: Vec<String>,
+    /// Maximum number of live instances created through `create_guarded`
+    #[serde(default)]
+    pub max_live_instances: Option<usize>,
+    /// Free-form key-value annotations (owner, ticket, SLA tier, ...)
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
+    /// Signature code hash at the time the module was approved
+    #[serde(default)]
+    pub approved_code_hash: Option<String>,
+    /// Type the factory must produce, checked on creation
+    #[serde(skip)]
+    pub expected_type_id: Option<TypeId>,
+}
+
+/// Modules deserialized without an `enabled` field are enabled
+fn default_enabled() -> bool {
+    true
+}
+
+/// Everything the registry knows about a module, except its factory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDescription {
+    /// Module metadata
+    pub metadata: ModuleMetadata,
+    /// Number of successful creations
+    pub creation_count: u64,
+    /// Unix timestamp of the last successful creation
+    pub last_created: Option<u64>,
+    /// Whether the module may be created
+    pub enabled: bool,
+    /// Aliases resolving to the module, sorted
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// Owned fields of a `ModuleMetadata`, for moving metadata without cloning
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleMetadataParts {
+    pub name: String,
+    pub module_type: String,
+    pub instantiate_fn_name: String,
+    pub module_path: String,
+    pub struct_name: String,
+    pub signature: Option<ModuleSignature>,
+    pub permissions: ModulePermissions,
+    pub review_status: CodeReviewStatus,
+    pub supply_chain: Option<SupplyChainInfo>,
+    pub sandbox_config: SandboxConfig,
+    pub last_modified: u64,
+    pub deprecated: Option<String>,
+    pub rate_limit: Option<(u32, Duration)>,
+    pub tags: Vec<String>,
+    pub singleton: bool,
+    pub enabled: bool,
+    pub dependencies: Vec<String>,
+    pub soft_dependencies: Vec<String>,
+    pub before_types: Vec<String>,
+    pub after_types: Vec<String>,
+    pub max_live_instances: Option<usize>,
+    pub annotations: HashMap<String, String>,
+    pub approved_code_hash: Option<String>,
+    pub expected_type_id: Option<TypeId>,
 }
 
 /// Security report for a module
@@ -163,11 +534,184 @@ pub struct SecurityReport {
     pub sandbox_enabled: bool,
 }
 
+/// Creation statistics for a module
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreationStats {
+    /// Number of successful creations
+    pub creation_count: u64,
+    /// Total time spent in successful factory calls
+    pub total_creation_time: Duration,
+    /// Unix timestamp of the last successful creation
+    pub last_created: Option<u64>,
+}
+
+/// Aggregate counts and security posture of a registry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegistryStatus {
+    /// Number of registered modules
+    pub total_modules: usize,
+    /// Number of modules per module type
+    pub by_type: HashMap<String, usize>,
+    /// Number of approved modules
+    pub approved: usize,
+    /// Number of modules pending review
+    pub pending: usize,
+    /// Number of modules under review
+    pub in_progress: usize,
+    /// Number of rejected modules
+    pub rejected: usize,
+    /// Number of modules failing the comprehensive security check
+    pub insecure: usize,
+    /// Number of disabled modules
+    #[serde(default)]
+    pub disabled: usize,
+}
+
+impl fmt::Display for RegistryStatus {
+    /// Render an aligned multi-line summary, with types sorted by name
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut by_type: Vec<(&String, &usize)> = self.by_type.iter().collect();
+        by_type.sort();
+
+        // Indented labels share one column so every count lines up
+        let width = by_type
+            .iter()
+            .map(|(module_type, _)| module_type.len())
+            .fold("In progress:".len(), usize::max);
+
+        writeln!(
+            f,
+            "{:<w$}{:>6}",
+            "Modules:",
+            self.total_modules,
+            w = width + 2
+        )?;
+        for (label, count) in [
+            ("Approved:", self.approved),
+            ("Pending:", self.pending),
+            ("In progress:", self.in_progress),
+            ("Rejected:", self.rejected),
+            ("Insecure:", self.insecure),
+            ("Disabled:", self.disabled),
+        ] {
+            writeln!(f, "  {:<w$}{:>6}", label, count, w = width)?;
+        }
+
+        write!(f, "{:<w$}{:>6}", "Types:", by_type.len(), w = width + 2)?;
+        for (module_type, count) in by_type {
+            write!(f, "\n  {:<w$}{:>6}", module_type, count, w = width)?;
+        }
+        Ok(())
+    }
+}
+
+/// Node of the module type hierarchy built by `ModuleRegistry::type_tree`
+///
+/// Module types are split on `.`; `codec.audio.opus` becomes the path
+/// `codec` → `audio` → `opus`, and the module is listed on the `opus` node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeTreeNode {
+    /// Child type segments, sorted by name
+    pub children: BTreeMap<String, TypeTreeNode>,
+    /// Names of modules whose type ends at this node, sorted
+    pub modules: Vec<String>,
+}
+
+impl TypeTreeNode {
+    /// Get the node at a dot-delimited type path below this node
+    pub fn get(&self, path: &str) -> Option<&TypeTreeNode> {
+        path.split('.')
+            .try_fold(self, |node, segment| node.children.get(segment))
+    }
+}
+
+/// How to handle a module whose name is already registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Keep the existing module
+    #[default]
+    Skip,
+    /// Replace the existing module
+    Overwrite,
+    /// Fail without loading anything
+    Error,
+}
+
+/// Order used by `ModuleRegistry::list_sorted_by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortKey {
+    /// Module name
+    Name,
+    /// Module type
+    Type,
+    /// Struct name
+    StructName,
+    /// Order in which modules were registered, oldest first
+    RegistrationIndex,
+}
+
+/// Outcome of loading a batch of modules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadReport {
+    /// Number of newly registered modules
+    pub loaded: usize,
+    /// Number of modules skipped because the name was taken
+    pub skipped: usize,
+    /// Number of existing modules replaced
+    pub overwritten: usize,
+    /// Files that could not be read, parsed or registered, with the error message
+    #[serde(default)]
+    pub failed: Vec<(PathBuf, String)>,
+}
+
 /// Factory function type for module instantiation
 /// Returns Box<dyn Any + Send + Sync> so it can work with any trait object
 pub type ModuleFactory = fn() -> Result<Box<dyn Any + Send + Sync>, anyhow::Error>;
 
+/// Precondition checked before a module's factory runs, e.g. a license check
+pub type Precondition = fn() -> Result<()>;
+
+/// Check run on every new instance of a module before it is returned
+pub type InstanceValidator = fn(&(dyn Any + Send + Sync)) -> Result<()>;
+
+/// Teardown hook run on a module instance by `ModuleRegistry::finalize`
+pub type Finalizer = fn(&(dyn Any + Send + Sync)) -> Result<()>;
+
+/// Time source used by the registry
+///
+/// Replace the default `SystemClock` with a manual clock to test time-dependent behavior.
+pub trait Clock: Send + Sync {
+    /// Get the current time
+    fn now(&self) -> SystemTime;
+}
+
+/// Clock backed by the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Configuration schema provider, typically `<YourModule as ConfigSchema>::schema`
+pub type SchemaProvider = fn() -> serde_json::Value;
+
+/// Decorator applied to metadata before it is inserted into the registry
+pub type MetadataDecorator = Arc<dyn Fn(&mut ModuleMetadata) + Send + Sync>;
+
+/// Function normalizing module names into registry keys
+pub type KeyNormalizer = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Interceptor wrapping every factory invocation made by `create_any`
+///
+/// Receives the module name and the raw factory, and is responsible for calling it.
+pub type FactoryInterceptor =
+    Arc<dyn Fn(&str, ModuleFactory) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync>;
+
 /// Registration entry for inventory collection
+#[cfg(feature = "inventory")]
 pub struct ModuleRegistration {
     pub name: &'static str,
     pub module_type: &'static str,
@@ -197,10 +741,25 @@ impl ModuleMetadata {
             review_status: CodeReviewStatus::Pending,
             supply_chain: None,
             sandbox_config: SandboxConfig::default(),
+            last_modified: current_timestamp(),
+            deprecated: None,
+            rate_limit: None,
+            tags: Vec::new(),
+            singleton: false,
+            enabled: true,
+            dependencies: Vec::new(),
+            soft_dependencies: Vec::new(),
+            before_types: Vec::new(),
+            after_types: Vec::new(),
+            max_live_instances: None,
+            annotations: HashMap::new(),
+            approved_code_hash: None,
+            expected_type_id: None,
         }
     }
 
     /// Create a secure module metadata
+    #[allow(clippy::too_many_arguments)]
     pub fn secure(
         name: String,
         module_type: String,
@@ -212,19 +771,96 @@ impl ModuleMetadata {
         supply_chain: Option<SupplyChainInfo>,
     ) -> Self {
         Self {
-            name,
-            module_type,
-            instantiate_fn_name,
-            module_path,
-            struct_name,
             signature,
             permissions,
-            review_status: CodeReviewStatus::Pending,
             supply_chain,
-            sandbox_config: SandboxConfig::default(),
+            ..Self::new(name, module_type, instantiate_fn_name, module_path, struct_name)
+        }
+    }
+
+    /// Create baseline metadata from a module's own name and type
+    ///
+    /// The remaining fields use the same defaults as `ModuleRegistry::register`.
+    pub fn from_module(m: &dyn Module) -> Self {
+        Self::new(
+            m.name().to_string(),
+            m.module_type().to_string(),
+            "factory".to_string(),
+            module_path!().to_string(),
+            "Module".to_string(),
+        )
+    }
+
+    /// Decompose the metadata into its owned fields
+    pub fn into_parts(self) -> ModuleMetadataParts {
+        ModuleMetadataParts {
+            name: self.name,
+            module_type: self.module_type,
+            instantiate_fn_name: self.instantiate_fn_name,
+            module_path: self.module_path,
+            struct_name: self.struct_name,
+            signature: self.signature,
+            permissions: self.permissions,
+            review_status: self.review_status,
+            supply_chain: self.supply_chain,
+            sandbox_config: self.sandbox_config,
+            last_modified: self.last_modified,
+            deprecated: self.deprecated,
+            rate_limit: self.rate_limit,
+            tags: self.tags,
+            singleton: self.singleton,
+            enabled: self.enabled,
+            dependencies: self.dependencies,
+            soft_dependencies: self.soft_dependencies,
+            before_types: self.before_types,
+            after_types: self.after_types,
+            max_live_instances: self.max_live_instances,
+            annotations: self.annotations,
+            approved_code_hash: self.approved_code_hash,
+            expected_type_id: self.expected_type_id,
+        }
+    }
+
+    /// Rebuild metadata from its owned fields
+    pub fn from_parts(parts: ModuleMetadataParts) -> Self {
+        Self {
+            name: parts.name,
+            module_type: parts.module_type,
+            instantiate_fn_name: parts.instantiate_fn_name,
+            module_path: parts.module_path,
+            struct_name: parts.struct_name,
+            signature: parts.signature,
+            permissions: parts.permissions,
+            review_status: parts.review_status,
+            supply_chain: parts.supply_chain,
+            sandbox_config: parts.sandbox_config,
+            last_modified: parts.last_modified,
+            deprecated: parts.deprecated,
+            rate_limit: parts.rate_limit,
+            tags: parts.tags,
+            singleton: parts.singleton,
+            enabled: parts.enabled,
+            dependencies: parts.dependencies,
+            soft_dependencies: parts.soft_dependencies,
+            before_types: parts.before_types,
+            after_types: parts.after_types,
+            max_live_instances: parts.max_live_instances,
+            annotations: parts.annotations,
+            approved_code_hash: parts.approved_code_hash,
+            expected_type_id: parts.expected_type_id,
         }
     }
 
+    /// Mark the metadata as modified now
+    pub fn touch(&mut self) {
+        self.touch_at(current_timestamp());
+    }
+
+    /// Mark the metadata as modified at the given Unix timestamp
+    pub fn touch_at(&mut self, timestamp: u64) {
+        self.last_modified = timestamp;
+    }
+
     /// Check if the module has valid signature
     pub fn has_valid_signature(&self) -> bool {
         self.signature.is_some()
@@ -235,6 +871,56 @@ impl ModuleMetadata {
         matches!(self.review_status, CodeReviewStatus::Approved { .. })
     }
 
+    /// Check if the module carries the given tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Check if the module is deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated.is_some()
+    }
+
+    /// Find permissions that the module's own sandbox configuration blocks
+    ///
+    /// Returns one warning per contradiction; a disabled sandbox blocks nothing.
+    pub fn validate_consistency(&self) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+        let sandbox = &self.sandbox_config;
+        if !sandbox.enabled {
+            return warnings;
+        }
+
+        let mut flag = |message: String| {
+            warnings.push(SecurityWarning {
+                message,
+                component: "consistency".to_string(),
+            })
+        };
+
+        if self.permissions.network_access && sandbox.network_isolation {
+            flag("Network access requested but the sandbox isolates the network".to_string());
+        }
+        if self.permissions.process_spawn && sandbox.process_isolation {
+            flag("Process spawning requested but the sandbox isolates processes".to_string());
+        }
+        if self.permissions.filesystem_access && sandbox.allowed_paths.is_empty() {
+            if sandbox.filesystem_isolation {
+                flag("Filesystem access requested but the filesystem is isolated".to_string());
+            }
+            if sandbox.read_only_fs {
+                flag("Filesystem access requested but the filesystem is read-only".to_string());
+            }
+        }
+        for path in &sandbox.allowed_paths {
+            if sandbox.denied_paths.contains(path) {
+                flag(format!("Sandbox path is both allowed and denied: {}", path));
+            }
+        }
+
+        warnings
+    }
+
     /// Check if the module has supply chain info
     pub fn has_supply_chain(&self) -> bool {
         self.supply_chain.is_some()
@@ -253,3 +939,11 @@ impl ModuleMetadata {
         )
     }
 }
+
+/// Current Unix timestamp in seconds
+pub(crate) fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
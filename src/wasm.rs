@@ -0,0 +1,115 @@
+//! WASM module factories backed by `wasmtime`
+//!
+//! A `WasmModuleFactory` compiles a WASM binary once and instantiates it for every
+//! `create_any`. Instances get no host imports, so filesystem, network and process
+//! isolation always hold; the module's `memory_limit_mb` caps its linear memory.
+//! Instantiation and every `WasmInstance::call` get a fuel budget of
+//! `timeout_seconds` at `cpu_limit_percent` of `WASM_FUEL_PER_CPU_SECOND`; a call
+//! that runs out traps instead of blocking the instance.
+
+use anyhow::{Context, Result};
+use std::any::Any;
+use std::sync::Mutex;
+use wasmtime::{Config, Engine, Instance, Linker, Store, StoreLimits, StoreLimitsBuilder, Val};
+
+use crate::constants::WASM_FUEL_PER_CPU_SECOND;
+use crate::types::ModulePermissions;
+
+/// Compiled WASM module that instantiates sandboxed `WasmInstance`s
+pub struct WasmModuleFactory {
+    engine: Engine,
+    module: wasmtime::Module,
+}
+
+/// Instantiated WASM module, returned boxed by `create_any`
+///
+/// Downcast the created module to `WasmInstance` and call its exports with `call`.
+pub struct WasmInstance {
+    store: Mutex<Store<StoreLimits>>,
+    instance: Instance,
+    fuel: u64,
+}
+
+impl WasmModuleFactory {
+    /// Compile a WASM binary
+    pub fn new(wasm_bytes: &[u8]) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).context("Failed to create WASM engine")?;
+        let module =
+            wasmtime::Module::new(&engine, wasm_bytes).context("Failed to compile WASM module")?;
+        Ok(Self { engine, module })
+    }
+
+    /// Instantiate the module with memory and fuel limited by `permissions`
+    ///
+    /// Fails if the module imports anything, since no host functions are provided.
+    pub fn instantiate(
+        &self,
+        permissions: &ModulePermissions,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        let memory_limit = usize::try_from(permissions.memory_limit_mb.saturating_mul(1024 * 1024))
+            .unwrap_or(usize::MAX);
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(memory_limit)
+            .instances(1)
+            .trap_on_grow_failure(true)
+            .build();
+
+        let fuel = fuel_budget(permissions);
+
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(fuel)?;
+        let instance = Linker::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .context("Failed to instantiate WASM module")?;
+
+        Ok(Box::new(WasmInstance {
+            store: Mutex::new(store),
+            instance,
+            fuel,
+        }))
+    }
+}
+
+impl WasmInstance {
+    /// Call an exported function, returning its results
+    ///
+    /// Each call starts with the instance's full fuel budget and traps once it is spent.
+    pub fn call(&self, export: &str, args: &[Val]) -> Result<Vec<Val>> {
+        let mut store = self
+            .store
+            .lock()
+            .expect("Failed to acquire WASM store lock");
+        store.set_fuel(self.fuel)?;
+        let func = self
+            .instance
+            .get_func(&mut *store, export)
+            .with_context(|| format!("WASM module has no exported function: {}", export))?;
+
+        let mut results = vec![Val::I32(0); func.ty(&*store).results().len()];
+        func.call(&mut *store, args, &mut results)
+            .with_context(|| format!("WASM call failed: {}", export))?;
+        Ok(results)
+    }
+
+    /// Names of the module's exports
+    pub fn exports(&self) -> Vec<String> {
+        let mut store = self
+            .store
+            .lock()
+            .expect("Failed to acquire WASM store lock");
+        self.instance
+            .exports(&mut *store)
+            .map(|export| export.name().to_string())
+            .collect()
+    }
+}
+
+/// Fuel for `timeout_seconds` of execution at `cpu_limit_percent` of one CPU
+fn fuel_budget(permissions: &ModulePermissions) -> u64 {
+    (WASM_FUEL_PER_CPU_SECOND / 100)
+        .saturating_mul(u64::from(permissions.cpu_limit_percent.min(100)))
+        .saturating_mul(permissions.timeout_seconds)
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+
+struct Echo;
+
+impl Module for Echo {
+    fn name(&self) -> &str {
+        "echo-instance"
+    }
+
+    fn module_type(&self) -> &str {
+        "plugin"
+    }
+}
+
+fn echo() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(Box::new(Echo) as Box<dyn Module>))
+}
+
+fn registry() -> ModuleRegistry {
+    let registry = ModuleRegistry::new();
+    registry.register("echo", "plugin", echo).unwrap();
+    registry.register("alpha", "plugin", echo).unwrap();
+    registry
+}
+
+#[test]
+fn list_command_returns_sorted_names() {
+    let response = registry().handle_command(RegistryCommand::List);
+    assert!(matches!(response, RegistryResponse::Modules(names) if names == ["alpha", "echo"]));
+}
+
+#[test]
+fn describe_command_describes_known_modules() {
+    let registry = registry();
+    let response = registry.handle_command(RegistryCommand::Describe {
+        name: "echo".to_string(),
+    });
+    assert!(
+        matches!(response, RegistryResponse::Description(description) if description.metadata.name == "echo")
+    );
+
+    let response = registry.handle_command(RegistryCommand::Describe {
+        name: "missing".to_string(),
+    });
+    assert!(matches!(response, RegistryResponse::Error(_)));
+}
+
+#[test]
+fn create_command_reports_the_module_name() {
+    let registry = registry();
+    let response = registry.handle_command(RegistryCommand::Create {
+        name: "echo".to_string(),
+    });
+    assert!(matches!(
+        response,
+        RegistryResponse::Created { name, module_name: Some(module_name) }
+            if name == "echo" && module_name == "echo-instance"
+    ));
+
+    let response = registry.handle_command(RegistryCommand::Create {
+        name: "missing".to_string(),
+    });
+    assert!(matches!(response, RegistryResponse::Error(_)));
+}
+
+#[test]
+fn audit_command_covers_every_module() {
+    let response = registry().handle_command(RegistryCommand::Audit);
+    assert!(matches!(response, RegistryResponse::Audit(results) if results.len() == 2));
+}
+
+#[test]
+fn count_command_counts_modules() {
+    let response = registry().handle_command(RegistryCommand::Count);
+    assert!(matches!(response, RegistryResponse::Count(2)));
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+static LAZY_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn slow_factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    LAZY_CALLS.fetch_add(1, Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(20));
+    Ok(Box::new(42u32))
+}
+
+fn entries(prefix: &str) -> Vec<(ModuleMetadata, ModuleFactory)> {
+    (0..10)
+        .map(|i| {
+            let metadata = ModuleMetadata::new(
+                format!("{prefix}{i}"),
+                "plugin".to_string(),
+                "factory".to_string(),
+                "tests/concurrency.rs".to_string(),
+                "Module".to_string(),
+            );
+            (metadata, factory as ModuleFactory)
+        })
+        .collect()
+}
+
+#[test]
+fn replace_all_is_never_observed_half_done() {
+    let registry = ModuleRegistry::new();
+    registry.replace_all(entries("old-")).unwrap();
+    let done = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                while !done.load(Ordering::SeqCst) {
+                    let names = registry.list_modules();
+                    assert_eq!(names.len(), 10);
+                    let old = names.iter().filter(|name| name.starts_with("old-")).count();
+                    assert!(old == 0 || old == 10, "mixed module set: {names:?}");
+                }
+            });
+        }
+
+        for round in 0..200 {
+            let prefix = if round % 2 == 0 { "new-" } else { "old-" };
+            registry.replace_all(entries(prefix)).unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+    });
+}
+
+/// Runs under both map backings; `cargo test --features arc-swap` covers the swap one
+#[test]
+fn registry_operations_match_across_map_backings() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "plugin", factory).unwrap();
+    registry.register("b", "plugin", factory).unwrap();
+    registry.rename("b", "c").unwrap();
+    registry.set_enabled("a", false).unwrap();
+
+    assert_eq!(registry.list_modules_sorted(), ["a", "c"]);
+    assert!(registry.create_any("a").is_err());
+    assert!(registry.create_any("c").is_ok());
+    assert_eq!(registry.list_enabled(), ["c"]);
+
+    registry.replace_all(entries("new-")).unwrap();
+    assert_eq!(registry.count(), 10);
+    assert!(!registry.has_module("c"));
+}
+
+#[test]
+fn get_lazy_runs_the_factory_once_across_threads() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register("expensive", "plugin", slow_factory)
+        .unwrap();
+
+    let instances: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..16)
+            .map(|_| scope.spawn(|| registry.get_lazy("expensive").unwrap()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert_eq!(LAZY_CALLS.load(Ordering::SeqCst), 1);
+    assert!(instances.iter().all(|i| Arc::ptr_eq(i, &instances[0])));
+    assert_eq!(instances[0].downcast_ref::<u32>(), Some(&42));
+
+    let later = registry.get_lazy("expensive").unwrap();
+    assert!(Arc::ptr_eq(&later, &instances[0]));
+    assert_eq!(LAZY_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn get_lazy_cache_hits_take_no_rate_token() {
+    let registry = ModuleRegistry::new();
+    registry.register("cached", "plugin", factory).unwrap();
+    registry
+        .set_rate_limit("cached", 1, Duration::from_secs(60))
+        .unwrap();
+
+    let first = registry.get_lazy("cached").unwrap();
+    for _ in 0..5 {
+        assert!(Arc::ptr_eq(&registry.get_lazy("cached").unwrap(), &first));
+    }
+}
+
+static PANICS_LEFT: AtomicUsize = AtomicUsize::new(1);
+
+fn panics_once() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    if PANICS_LEFT
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok()
+    {
+        panic!("factory panicked");
+    }
+    Ok(Box::new(7u32))
+}
+
+#[test]
+fn get_lazy_retries_after_a_panicking_factory() {
+    let registry = ModuleRegistry::new();
+    registry.register("flaky", "plugin", panics_once).unwrap();
+
+    let panicked = thread::scope(|scope| scope.spawn(|| registry.get_lazy("flaky")).join());
+    assert!(panicked.is_err());
+
+    let instance = registry.get_lazy("flaky").unwrap();
+    assert_eq!(instance.downcast_ref::<u32>(), Some(&7));
+}
+
+static REENTRANT: std::sync::OnceLock<ModuleRegistry> = std::sync::OnceLock::new();
+
+fn reentrant_factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    let inner = REENTRANT.get().unwrap().get_lazy("reentrant")?;
+    Ok(Box::new(inner))
+}
+
+#[test]
+fn reentrant_get_lazy_fails_instead_of_deadlocking() {
+    let registry = REENTRANT.get_or_init(ModuleRegistry::new);
+    registry
+        .register("reentrant", "plugin", reentrant_factory)
+        .unwrap();
+
+    let err = registry.get_lazy("reentrant").unwrap_err();
+    assert!(err.chain().any(|cause| matches!(
+        cause.downcast_ref::<RegistryError>(),
+        Some(RegistryError::DependencyCycle(name)) if name == "reentrant"
+    )));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "core")]
+
+use module_registry::{CoreError, CoreRegistry};
+use std::any::Any;
+
+fn factory() -> Result<Box<dyn Any + Send + Sync>, CoreError> {
+    Ok(Box::new(7u32))
+}
+
+#[test]
+fn core_registry_registers_and_creates() {
+    let mut registry = CoreRegistry::new();
+    registry.register("counter", "plugin", factory).unwrap();
+
+    assert!(registry.has_module("counter"));
+    assert_eq!(registry.list_modules(), ["counter"]);
+    let instance = registry.create_any("counter").unwrap();
+    assert_eq!(instance.downcast_ref::<u32>(), Some(&7));
+    assert!(matches!(
+        registry.create_any("missing"),
+        Err(CoreError::NotFound(_))
+    ));
+}
+
+#[test]
+fn core_registry_validates_names_like_module_registry() {
+    let mut registry = CoreRegistry::new();
+    registry.register("  padded  ", "plugin", factory).unwrap();
+
+    assert_eq!(registry.list_modules(), ["padded"]);
+    for name in ["", "   ", "line\nbreak", "tab\tname"] {
+        assert_eq!(
+            registry.register(name, "plugin", factory),
+            Err(CoreError::InvalidName(name.to_string()))
+        );
+    }
+    assert_eq!(registry.count(), 1);
+}
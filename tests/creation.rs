@@ -0,0 +1,808 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+/// Registry whose key normalizer strips one `x-` prefix, so it is not idempotent
+fn prefix_stripping_registry() -> ModuleRegistry {
+    let registry = ModuleRegistry::new();
+    registry.set_key_normalizer(Arc::new(|name: &str| {
+        name.strip_prefix("x-").unwrap_or(name).to_string()
+    }));
+    registry.register("x-x-foo", "plugin", factory).unwrap();
+    registry
+}
+
+#[test]
+fn create_where_creates_matching_modules() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "alpha", factory).unwrap();
+    registry.register("b", "beta", factory).unwrap();
+    registry.register("c", "alpha", factory).unwrap();
+
+    let mut created: Vec<String> = registry
+        .create_where(|metadata| metadata.module_type == "alpha")
+        .into_iter()
+        .map(|(name, result)| {
+            assert!(result.is_ok());
+            name
+        })
+        .collect();
+    created.sort();
+    assert_eq!(created, ["a", "c"]);
+}
+
+#[test]
+fn create_where_uses_stored_keys() {
+    let registry = prefix_stripping_registry();
+    let results = registry.create_where(|_| true);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_ok(), "{:?}", results[0].1.as_ref().err());
+}
+
+#[test]
+fn create_all_throttled_uses_stored_keys() {
+    let registry = prefix_stripping_registry();
+    let results = registry.create_all_throttled(2);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_ok(), "{:?}", results[0].1.as_ref().err());
+}
+
+#[test]
+fn visit_all_uses_stored_keys() {
+    let registry = prefix_stripping_registry();
+    let mut visited = Vec::new();
+    let results = registry.visit_all(|name, _| visited.push(name.to_string()));
+    assert!(results[0].1.is_ok(), "{:?}", results[0].1.as_ref().err());
+    assert_eq!(visited, ["x-foo"]);
+}
+
+#[test]
+fn create_chain_uses_stored_keys() {
+    let registry = prefix_stripping_registry();
+    let chain = registry.create_chain("x-x-foo").unwrap();
+    assert_eq!(chain.len(), 1);
+}
+
+fn seven() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(7u32))
+}
+
+#[test]
+fn override_scope_restores_factory() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "plugin", factory).unwrap();
+    {
+        let _guard = registry.override_scope("a", seven);
+        assert_eq!(*registry.create::<u32>("a").unwrap(), 7);
+    }
+    assert_eq!(*registry.create::<u32>("a").unwrap(), 0);
+}
+
+#[test]
+fn override_scope_removes_temporary_module() {
+    let registry = ModuleRegistry::new();
+    {
+        let _guard = registry.override_scope("a", seven);
+        assert_eq!(*registry.create::<u32>("a").unwrap(), 7);
+    }
+    assert!(!registry.has_module("a"));
+}
+
+#[test]
+fn override_scope_restores_a_renamed_module() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "plugin", factory).unwrap();
+    {
+        let _guard = registry.override_scope("a", seven);
+        registry.rename("a", "b").unwrap();
+        assert_eq!(*registry.create::<u32>("b").unwrap(), 7);
+    }
+    assert_eq!(*registry.create::<u32>("b").unwrap(), 0);
+}
+
+#[test]
+fn override_scope_removes_a_renamed_temporary_module() {
+    let registry = ModuleRegistry::new();
+    {
+        let _guard = registry.override_scope("a", seven);
+        registry.rename("a", "b").unwrap();
+    }
+    assert!(registry.list_modules().is_empty());
+}
+
+#[test]
+fn override_scope_keeps_module_registered_inside_scope() {
+    let registry = ModuleRegistry::new();
+    {
+        let _guard = registry.override_scope("a", seven);
+        registry.register("a", "plugin", factory).unwrap();
+    }
+    assert_eq!(*registry.create::<u32>("a").unwrap(), 0);
+}
+
+#[test]
+fn refused_deprecated_create_leaves_rate_token() {
+    let registry = ModuleRegistry::new();
+    registry.register("limited", "plugin", factory).unwrap();
+    registry
+        .set_rate_limit("limited", 1, Duration::from_secs(3600))
+        .unwrap();
+    registry.mark_deprecated("limited", "use other").unwrap();
+
+    registry.set_deny_deprecated(true);
+    let err = registry.create_any("limited").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Deprecated { .. })
+    ));
+
+    registry.set_deny_deprecated(false);
+    assert!(registry.create_any("limited").is_ok());
+}
+
+#[test]
+fn refused_guarded_create_leaves_rate_token() {
+    let registry = ModuleRegistry::new();
+    registry.register("limited", "plugin", factory).unwrap();
+    registry
+        .set_rate_limit("limited", 2, Duration::from_secs(3600))
+        .unwrap();
+    registry.set_max_live_instances("limited", Some(1)).unwrap();
+
+    let first = registry.create_guarded("limited").unwrap();
+    let Err(err) = registry.create_guarded("limited") else {
+        panic!("second guarded instance exceeded the cap");
+    };
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::TooManyInstances { .. })
+    ));
+
+    drop(first);
+    assert!(registry.create_guarded("limited").is_ok());
+}
+
+#[test]
+fn create_all_transactional_reports_every_failure() {
+    let registry = ModuleRegistry::new();
+    registry.register("unsigned", "plugin", factory).unwrap();
+
+    let err = registry
+        .create_all_transactional(&["unsigned", "missing", "gone"], &SecurityPolicy::default())
+        .unwrap_err();
+    let Some(RegistryError::TransactionAborted { failures }) = err.downcast_ref() else {
+        panic!("expected TransactionAborted, got {err}");
+    };
+    assert!(matches!(
+        failures.as_slice(),
+        [
+            RegistryError::SecurityCheckFailed { .. },
+            RegistryError::NotFound(missing),
+            RegistryError::NotFound(gone),
+        ] if missing == "missing" && gone == "gone"
+    ));
+}
+
+#[test]
+fn factory_interceptor_logs_and_delegates() {
+    let registry = ModuleRegistry::new();
+    registry.register("seven", "plugin", seven).unwrap();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let seen = log.clone();
+    registry.set_factory_interceptor(Arc::new(move |name, factory| {
+        seen.lock().unwrap().push(name.to_string());
+        factory()
+    }));
+
+    let instance = registry.create_any("seven").unwrap();
+    assert_eq!(instance.downcast_ref::<u32>(), Some(&7));
+    assert_eq!(*log.lock().unwrap(), ["seven"]);
+}
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Factory recording how many of its calls overlap
+fn tracked() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    let active = ACTIVE.fetch_add(1, Ordering::SeqCst) + 1;
+    PEAK.fetch_max(active, Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(10));
+    ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    Ok(Box::new(0u32))
+}
+
+#[test]
+fn create_all_throttled_respects_the_limit() {
+    let registry = ModuleRegistry::new();
+    for i in 0..8 {
+        registry
+            .register(&format!("tracked-{i}"), "plugin", tracked)
+            .unwrap();
+    }
+
+    let results = registry.create_all_throttled(3);
+
+    assert_eq!(results.len(), 8);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    let peak = PEAK.load(Ordering::SeqCst);
+    assert!((1..=3).contains(&peak), "peak concurrency {peak}");
+}
+
+#[test]
+fn deprecated_modules_can_still_be_created() {
+    let registry = ModuleRegistry::new();
+    registry.register("legacy", "plugin", factory).unwrap();
+    registry.mark_deprecated("legacy", "use modern").unwrap();
+
+    assert!(registry.create_any("legacy").is_ok());
+    assert_eq!(
+        registry.deprecated_modules(),
+        [("legacy".to_string(), "use modern".to_string())]
+    );
+}
+
+#[test]
+fn reset_stats_keeps_modules_registered() {
+    let registry = ModuleRegistry::new();
+    registry.register("counted", "plugin", factory).unwrap();
+    for _ in 0..3 {
+        registry.create_any("counted").unwrap();
+    }
+    assert_eq!(
+        registry.creation_stats("counted").unwrap().creation_count,
+        3
+    );
+
+    registry.reset_stats();
+
+    assert_eq!(
+        registry.creation_stats("counted").unwrap().creation_count,
+        0
+    );
+    assert!(registry.has_module("counted"));
+}
+
+trait Greeter: Send + Sync {
+    fn greet(&self) -> String;
+}
+
+struct English;
+
+impl Greeter for English {
+    fn greet(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+/// Factory following the double-box convention
+fn english() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(Box::new(English) as Box<dyn Greeter>))
+}
+
+#[test]
+fn downcast_module_borrows_the_trait_object() {
+    let registry = ModuleRegistry::new();
+    registry.register("english", "greeter", english).unwrap();
+    let instance = registry.create_any("english").unwrap();
+
+    let greeter = downcast_module::<dyn Greeter>(&*instance).unwrap();
+    assert_eq!(greeter.greet(), "hello");
+}
+
+#[test]
+fn downcast_module_rejects_other_types() {
+    let registry = ModuleRegistry::new();
+    registry.register("number", "plugin", factory).unwrap();
+    let instance = registry.create_any("number").unwrap();
+
+    assert!(downcast_module::<dyn Greeter>(&*instance).is_none());
+}
+
+/// Clock that only moves when advanced
+struct ManualClock(Mutex<SystemTime>);
+
+impl ManualClock {
+    fn new() -> Arc<Self> {
+        Arc::new(Self(Mutex::new(SystemTime::UNIX_EPOCH)))
+    }
+
+    fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[test]
+fn rate_limit_refills_after_the_window() {
+    let clock = ManualClock::new();
+    let registry = ModuleRegistry::builder().clock(clock.clone()).build();
+    registry.register("limited", "plugin", factory).unwrap();
+    registry
+        .set_rate_limit("limited", 2, Duration::from_secs(60))
+        .unwrap();
+
+    assert!(registry.create_any("limited").is_ok());
+    assert!(registry.create_any("limited").is_ok());
+    let err = registry.create_any("limited").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::RateLimited { .. })
+    ));
+
+    clock.advance(Duration::from_secs(60));
+    assert!(registry.create_any("limited").is_ok());
+}
+
+#[test]
+fn singleton_creations_share_one_allocation() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_singleton("config", "service", factory)
+        .unwrap();
+
+    let first = registry.create_shared("config").unwrap();
+    let second = registry.create_shared("config").unwrap();
+
+    assert!(Arc::ptr_eq(&first, &second));
+    let err = registry.create_any("config").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Singleton(_))
+    ));
+}
+
+#[test]
+fn replace_factory_keeps_metadata() {
+    let registry = ModuleRegistry::new();
+    registry.register("swappable", "plugin", factory).unwrap();
+    let before = registry.get_metadata("swappable").unwrap();
+
+    registry.replace_factory("swappable", seven).unwrap();
+
+    assert_eq!(registry.get_metadata("swappable").unwrap(), before);
+    let instance = registry.create_any("swappable").unwrap();
+    assert_eq!(instance.downcast_ref::<u32>(), Some(&7));
+}
+
+#[test]
+fn create_where_skips_unapproved_modules() {
+    let registry = ModuleRegistry::new();
+    registry.register("approved", "plugin", factory).unwrap();
+    registry.register("pending", "plugin", factory).unwrap();
+    registry
+        .update_review_status(
+            "approved",
+            CodeReviewStatus::Approved {
+                reviewer: "alice".to_string(),
+                timestamp: 0,
+            },
+        )
+        .unwrap();
+
+    let created = registry.create_where(ModuleMetadata::is_approved);
+
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].0, "approved");
+    assert_eq!(registry.unused_modules(), ["pending"]);
+}
+
+#[test]
+fn disabled_modules_refuse_creation_until_enabled() {
+    let registry = ModuleRegistry::new();
+    registry.register("toggled", "plugin", factory).unwrap();
+
+    registry.set_enabled("toggled", false).unwrap();
+    let err = registry.create_any("toggled").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Disabled(_))
+    ));
+    assert!(!registry.get_metadata("toggled").unwrap().enabled);
+
+    registry.set_enabled("toggled", true).unwrap();
+    assert!(registry.create_any("toggled").is_ok());
+}
+
+#[test]
+fn register_typed_rejects_the_wrong_type() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_typed::<String>("named", "plugin", factory)
+        .unwrap();
+    registry
+        .register_typed::<u32>("counter", "plugin", factory)
+        .unwrap();
+
+    let err = registry.create_any("named").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::TypeAssertionFailed(_))
+    ));
+    assert!(registry.create_any("counter").is_ok());
+}
+
+#[test]
+fn not_found_error_suggests_registered_modules() {
+    let registry = ModuleRegistry::new();
+    registry.register("audio-codec", "codec", factory).unwrap();
+
+    let err = registry.create_any("audio-codex").unwrap_err();
+
+    assert!(err.to_string().contains("audio-codec"), "{err}");
+}
+
+#[test]
+fn create_all_transactional_creates_nothing_on_failure() {
+    let registry = ModuleRegistry::new();
+    registry.register("safe", "plugin", factory).unwrap();
+    let mut metadata = ModuleMetadata::new(
+        "unsandboxed".to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "tests/creation.rs".to_string(),
+        "Module".to_string(),
+    );
+    metadata.permissions.system_access = true;
+    metadata.sandbox_config.enabled = false;
+    registry.register_metadata(metadata, factory).unwrap();
+
+    let policy = SecurityPolicy::permissive();
+    assert!(registry
+        .create_all_transactional(&["safe", "unsandboxed"], &policy)
+        .is_err());
+    assert_eq!(registry.creation_stats("safe").unwrap().creation_count, 0);
+    assert_eq!(
+        registry
+            .create_all_transactional(&["safe"], &policy)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn guarded_instances_are_capped_until_dropped() {
+    let registry = ModuleRegistry::new();
+    registry.register("pooled", "plugin", factory).unwrap();
+    registry.set_max_live_instances("pooled", Some(2)).unwrap();
+
+    let first = registry.create_guarded("pooled").unwrap();
+    let _second = registry.create_guarded("pooled").unwrap();
+    let Err(err) = registry.create_guarded("pooled") else {
+        panic!("third guarded instance exceeded the cap");
+    };
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::TooManyInstances { max: 2, .. })
+    ));
+
+    drop(first);
+    assert!(registry.create_guarded("pooled").is_ok());
+}
+
+static GATED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn gated() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    GATED_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok(Box::new(0u32))
+}
+
+fn never_ready() -> anyhow::Result<()> {
+    anyhow::bail!("database unavailable")
+}
+
+#[test]
+fn failed_precondition_skips_the_factory() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_with_precondition("gated", "plugin", gated, never_ready)
+        .unwrap();
+
+    let err = registry.create_any("gated").unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::PreconditionFailed { .. })
+    ));
+    assert_eq!(GATED_CALLS.load(Ordering::SeqCst), 0);
+}
+
+fn broken() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    anyhow::bail!("connection refused")
+}
+
+#[test]
+fn repeated_factory_failures_disable_the_module() {
+    let registry = ModuleRegistry::builder().failure_threshold(3).build();
+    registry.register("flaky", "plugin", broken).unwrap();
+    let events = registry.subscribe();
+
+    for _ in 0..3 {
+        let err = registry.create_any("flaky").unwrap_err();
+        assert!(!matches!(
+            err.downcast_ref::<RegistryError>(),
+            Some(RegistryError::Disabled(_))
+        ));
+    }
+
+    let err = registry.create_any("flaky").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Disabled(_))
+    ));
+    let opened = std::iter::from_fn(|| events.try_recv()).any(|event| {
+        event
+            == RegistryEvent::CircuitOpened {
+                name: "flaky".to_string(),
+            }
+    });
+    assert!(opened);
+}
+
+#[test]
+fn create_group_creates_every_member() {
+    let registry = ModuleRegistry::new();
+    registry.register("decoder", "codec", factory).unwrap();
+    registry.register("encoder", "codec", factory).unwrap();
+    registry
+        .define_group("codecs", vec!["decoder".to_string(), "encoder".to_string()])
+        .unwrap();
+
+    let created: Vec<String> = registry
+        .create_group("codecs")
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    assert_eq!(created, ["decoder", "encoder"]);
+    assert_eq!(registry.list_groups(), ["codecs"]);
+}
+
+#[test]
+fn define_group_rejects_missing_members() {
+    let registry = ModuleRegistry::new();
+    registry.register("decoder", "codec", factory).unwrap();
+
+    let err = registry
+        .define_group("codecs", vec!["decoder".to_string(), "muxer".to_string()])
+        .unwrap_err();
+
+    assert!(format!("{err:#}").contains("muxer"), "{err:#}");
+    assert!(registry.list_groups().is_empty());
+    assert!(registry.create_group("codecs").is_err());
+}
+
+fn single_boxed_english() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(English))
+}
+
+fn double_boxed_seven() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(Box::new(7u32)))
+}
+
+#[test]
+fn create_hints_when_the_factory_does_not_double_box() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register("english", "greeter", single_boxed_english)
+        .unwrap();
+
+    let Err(err) = registry.create::<Box<dyn Greeter>>("english") else {
+        panic!("single-boxed module downcast to a trait object box");
+    };
+
+    let message = err.to_string();
+    assert!(message.contains("holds neither"), "{message}");
+    assert!(
+        message.contains("Box::new(Box::new(module) as Box<dyn Trait>)"),
+        "{message}"
+    );
+}
+
+#[test]
+fn create_hints_when_the_factory_double_boxes() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register("seven", "plugin", double_boxed_seven)
+        .unwrap();
+
+    let err = registry.create::<u32>("seven").unwrap_err();
+
+    assert!(err.to_string().contains("Box<u32>"), "{err}");
+    assert_eq!(**registry.create::<Box<u32>>("seven").unwrap(), 7);
+}
+
+#[test]
+fn visit_all_reaches_trait_objects_and_skips_others() {
+    let registry = ModuleRegistry::new();
+    registry.register("english", "greeter", english).unwrap();
+    registry.register("number", "plugin", factory).unwrap();
+
+    let mut greetings = Vec::new();
+    let mut unmatched = Vec::new();
+    let results =
+        registry.visit_all(
+            |name, instance| match downcast_module::<dyn Greeter>(instance) {
+                Some(greeter) => greetings.push(greeter.greet()),
+                None => unmatched.push(name.to_string()),
+            },
+        );
+
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(greetings, ["hello"]);
+    assert_eq!(unmatched, ["number"]);
+}
+
+fn doubled_seven(ctx: &RegistryContext<'_>) -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    let dep = ctx.registry().create::<u32>("seven")?;
+    Ok(Box::new(*dep * 2))
+}
+
+#[test]
+fn context_factory_resolves_its_dependency() {
+    let registry = ModuleRegistry::new();
+    registry.register("seven", "plugin", seven).unwrap();
+    registry
+        .register_with_context("doubled", "plugin", doubled_seven)
+        .unwrap();
+
+    assert_eq!(*registry.create::<u32>("doubled").unwrap(), 14);
+    let instance = registry.create_with_context("doubled", &()).unwrap();
+    assert_eq!(instance.downcast_ref::<u32>(), Some(&14));
+}
+
+fn empty() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(()))
+}
+
+fn reject_unit(instance: &(dyn Any + Send + Sync)) -> anyhow::Result<()> {
+    if instance.is::<()>() {
+        anyhow::bail!("factory returned an empty instance");
+    }
+    Ok(())
+}
+
+#[test]
+fn instance_validator_rejects_empty_payloads() {
+    let registry = ModuleRegistry::new();
+    registry.register("empty", "plugin", empty).unwrap();
+    registry.register("seven", "plugin", seven).unwrap();
+    registry
+        .set_instance_validator("empty", Some(reject_unit))
+        .unwrap();
+    registry
+        .set_instance_validator("seven", Some(reject_unit))
+        .unwrap();
+
+    let err = registry.create_any("empty").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidInstance { name, .. }) if name == "empty"
+    ));
+    assert!(registry.create_any("seven").is_ok());
+
+    registry.set_instance_validator("empty", None).unwrap();
+    assert!(registry.create_any("empty").is_ok());
+}
+
+#[test]
+fn deny_deprecated_refuses_creation_until_cleared() {
+    let registry = ModuleRegistry::builder().deny_deprecated(true).build();
+    registry.register("legacy", "plugin", factory).unwrap();
+    registry.register("current", "plugin", factory).unwrap();
+    registry.mark_deprecated("legacy", "use current").unwrap();
+
+    let err = registry.create_any("legacy").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Deprecated { name, note }) if name == "legacy" && note == "use current"
+    ));
+    assert!(registry.create_any("current").is_ok());
+
+    registry.set_deny_deprecated(false);
+    assert!(registry.create_any("legacy").is_ok());
+}
+
+#[test]
+fn can_create_as_reports_the_instance_type() {
+    let registry = ModuleRegistry::new();
+    registry.register("seven", "plugin", seven).unwrap();
+
+    assert!(registry.can_create_as::<u32>("seven"));
+    assert!(!registry.can_create_as::<String>("seven"));
+    assert!(!registry.can_create_as::<u32>("missing"));
+}
+
+#[test]
+fn create_or_else_falls_back_only_when_creation_fails() {
+    let registry = ModuleRegistry::new();
+    registry.register("seven", "plugin", seven).unwrap();
+    registry.register("flaky", "plugin", broken).unwrap();
+    let fallback = || Box::new(0u32) as Box<dyn Any + Send + Sync>;
+
+    let real = registry.create_or_else("seven", fallback);
+    let failed = registry.create_or_else("flaky", fallback);
+    let missing = registry.create_or_else("missing", fallback);
+
+    assert_eq!(real.downcast_ref::<u32>(), Some(&7));
+    assert_eq!(failed.downcast_ref::<u32>(), Some(&0));
+    assert_eq!(missing.downcast_ref::<u32>(), Some(&0));
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn factory_failure_carries_a_backtrace() {
+    use std::backtrace::BacktraceStatus;
+
+    let registry = ModuleRegistry::new();
+    registry.register("flaky", "plugin", broken).unwrap();
+
+    let err = registry.create_any("flaky").unwrap_err();
+    let Some(RegistryError::FactoryFailed {
+        name,
+        source,
+        backtrace,
+    }) = err.downcast_ref::<RegistryError>()
+    else {
+        panic!("expected FactoryFailed, got {err:?}");
+    };
+
+    assert_eq!(name, "flaky");
+    assert_eq!(source.to_string(), "connection refused");
+    assert_eq!(backtrace.status(), BacktraceStatus::Captured);
+    assert!(!backtrace.to_string().is_empty());
+}
+
+static CONFIG_CALLS: AtomicUsize = AtomicUsize::new(0);
+static CONNECTION_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn misconfigured() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    CONFIG_CALLS.fetch_add(1, Ordering::SeqCst);
+    anyhow::bail!("invalid config")
+}
+
+fn unreachable_peer() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    CONNECTION_CALLS.fetch_add(1, Ordering::SeqCst);
+    anyhow::bail!("connection refused")
+}
+
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    format!("{err:#}").contains("connection")
+}
+
+#[test]
+fn create_any_retry_if_retries_only_matching_errors() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register("config", "plugin", misconfigured)
+        .unwrap();
+    registry
+        .register("peer", "plugin", unreachable_peer)
+        .unwrap();
+
+    assert!(registry
+        .create_any_retry_if("config", 3, is_connection_error)
+        .is_err());
+    assert_eq!(CONFIG_CALLS.load(Ordering::SeqCst), 1);
+
+    assert!(registry
+        .create_any_retry_if("peer", 3, is_connection_error)
+        .is_err());
+    assert_eq!(CONNECTION_CALLS.load(Ordering::SeqCst), 3);
+}
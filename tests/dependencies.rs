@@ -0,0 +1,158 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::Mutex;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+/// Registry with modules `m0` to `m{len - 1}`, each depending on the next
+fn chain(len: usize) -> ModuleRegistry {
+    let registry = ModuleRegistry::new();
+    for i in 0..len {
+        registry
+            .register(&format!("m{i}"), "plugin", factory)
+            .unwrap();
+    }
+    for i in 1..len {
+        registry
+            .set_dependencies(&format!("m{}", i - 1), [format!("m{i}")])
+            .unwrap();
+    }
+    registry
+}
+
+#[test]
+fn dependency_chain_within_the_limit_orders() {
+    let registry = chain(4);
+    registry.set_max_dependency_depth(4);
+
+    assert_eq!(
+        registry.initialization_order().unwrap(),
+        ["m3", "m2", "m1", "m0"]
+    );
+}
+
+#[test]
+fn dependency_chain_over_the_limit_fails() {
+    let registry = chain(5);
+    registry.set_max_dependency_depth(4);
+
+    let err = registry.initialization_order().unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::DependencyTooDeep { depth: 5 })
+    ));
+}
+
+#[test]
+fn create_chain_creates_dependencies_first() {
+    let registry = ModuleRegistry::new();
+    for name in ["a", "b", "c"] {
+        registry.register(name, "plugin", factory).unwrap();
+    }
+    registry.set_dependencies("a", ["b", "c"]).unwrap();
+
+    let created: Vec<String> = registry
+        .create_chain("a")
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    assert_eq!(created, ["b", "c", "a"]);
+}
+
+#[test]
+fn missing_soft_dependency_is_skipped() {
+    let registry = ModuleRegistry::new();
+    registry.register("app", "plugin", factory).unwrap();
+    registry.register("cache", "plugin", factory).unwrap();
+    registry
+        .set_soft_dependencies("app", ["cache", "metrics"])
+        .unwrap();
+
+    assert_eq!(registry.initialization_order().unwrap(), ["cache", "app"]);
+}
+
+#[test]
+fn missing_hard_dependency_fails() {
+    let registry = ModuleRegistry::new();
+    registry.register("app", "plugin", factory).unwrap();
+    registry.set_dependencies("app", ["metrics"]).unwrap();
+
+    let err = registry.initialization_order().unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::MissingDependency { dependency, .. }) if dependency == "metrics"
+    ));
+}
+
+#[test]
+fn setup_module_orders_before_every_worker() {
+    let registry = ModuleRegistry::new();
+    registry.register("a-worker", "worker", factory).unwrap();
+    registry.register("b-worker", "worker", factory).unwrap();
+    registry.register("setup", "bootstrap", factory).unwrap();
+    registry
+        .set_type_ordering("setup", vec!["worker".to_string()], Vec::new())
+        .unwrap();
+
+    assert_eq!(
+        registry.initialization_order().unwrap(),
+        ["setup", "a-worker", "b-worker"]
+    );
+
+    let err = registry
+        .set_type_ordering("a-worker", vec!["worker".to_string()], Vec::new())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::OrderingContradiction { name, .. }) if name == "a-worker"
+    ));
+}
+
+static FINALIZED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_finalized(instance: &(dyn Any + Send + Sync)) -> anyhow::Result<()> {
+    let label = instance
+        .downcast_ref::<String>()
+        .ok_or_else(|| anyhow::anyhow!("unexpected instance"))?;
+    FINALIZED.lock().unwrap().push(label.clone());
+    Ok(())
+}
+
+#[test]
+fn finalizers_run_in_reverse_initialization_order() {
+    let registry = ModuleRegistry::new();
+    registry.register("app", "plugin", factory).unwrap();
+    registry.register("db", "plugin", factory).unwrap();
+    registry
+        .set_dependencies("app", ["db".to_string()])
+        .unwrap();
+    registry
+        .register_finalizer("app", record_finalized)
+        .unwrap();
+    registry.register_finalizer("db", record_finalized).unwrap();
+    assert_eq!(registry.initialization_order().unwrap(), ["db", "app"]);
+
+    let instances: Vec<(String, Box<dyn Any + Send + Sync>)> = ["db", "app"]
+        .into_iter()
+        .map(|name| (name.to_string(), Box::new(name.to_string()) as _))
+        .collect();
+    let results = registry
+        .finalize_all_in_reverse_init_order(&instances)
+        .unwrap();
+
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(
+        results
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        ["app", "db"]
+    );
+    assert_eq!(*FINALIZED.lock().unwrap(), ["app", "db"]);
+}
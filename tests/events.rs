@@ -0,0 +1,29 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::time::Duration;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+#[test]
+fn subscriber_receives_registrations() {
+    let registry = ModuleRegistry::new();
+    let events = registry.subscribe();
+
+    registry.register("a", "alpha", factory).unwrap();
+    registry.register("b", "beta", factory).unwrap();
+
+    for (name, module_type) in [("a", "alpha"), ("b", "beta")] {
+        assert_eq!(
+            events.recv_timeout(Duration::from_secs(1)),
+            Some(RegistryEvent::Registered {
+                name: name.to_string(),
+                module_type: module_type.to_string(),
+            })
+        );
+    }
+    assert_eq!(events.try_recv(), None);
+}
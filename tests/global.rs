@@ -0,0 +1,17 @@
+#![cfg(all(feature = "full", not(feature = "inventory")))]
+
+use module_registry::*;
+use std::any::Any;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+#[test]
+fn global_registry_starts_empty_without_inventory() {
+    let registry = ModuleRegistry::global();
+    assert!(registry.list_modules().is_empty());
+
+    registry.register("runtime", "plugin", factory).unwrap();
+    assert!(registry.create_any("runtime").is_ok());
+}
@@ -0,0 +1,79 @@
+#![cfg(all(feature = "full", feature = "inventory"))]
+
+use module_registry::{
+    duplicate_registration_names, register_module, ConflictPolicy, ModuleMetadata,
+    ModuleRegistration, ModuleRegistry,
+};
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+register_module!("inventory-test-module", "InventoryTestModule", factory);
+
+#[test]
+fn load_inventory_decorates_outside_the_module_lock() {
+    static REGISTRY: OnceLock<ModuleRegistry> = OnceLock::new();
+    static BLOCKED: AtomicBool = AtomicBool::new(false);
+
+    let registry = REGISTRY.get_or_init(ModuleRegistry::new);
+    registry.set_metadata_decorator(Arc::new(|_: &mut ModuleMetadata| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(REGISTRY.get().unwrap().has_module("anything"));
+        });
+        if rx.recv_timeout(Duration::from_secs(5)).is_err() {
+            BLOCKED.store(true, Ordering::SeqCst);
+        }
+    }));
+
+    registry.load_inventory_with(ConflictPolicy::Error).unwrap();
+    assert!(!BLOCKED.load(Ordering::SeqCst));
+    assert!(registry.has_module("inventory-test-module"));
+}
+
+#[test]
+fn load_inventory_skip_keeps_existing_modules() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register("inventory-test-module", "manual", factory)
+        .unwrap();
+
+    let report = registry.load_inventory_with(ConflictPolicy::Skip).unwrap();
+
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.loaded, 0);
+    let metadata = registry.get_metadata("inventory-test-module").unwrap();
+    assert_eq!(metadata.module_type, "manual");
+}
+
+fn registration(name: &'static str) -> ModuleRegistration {
+    ModuleRegistration {
+        name,
+        module_type: "module",
+        instantiate_fn_name: "factory",
+        module_path: "tests/inventory.rs",
+        struct_name: "Module",
+        factory,
+    }
+}
+
+#[test]
+fn duplicate_registration_names_flags_repeats() {
+    let registrations = [
+        registration("a"),
+        registration("b"),
+        registration("a"),
+        registration("c"),
+        registration("a"),
+        registration("c"),
+    ];
+
+    assert_eq!(duplicate_registration_names(&registrations), ["a", "c"]);
+}
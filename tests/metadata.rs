@@ -0,0 +1,106 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn metadata(name: &str) -> ModuleMetadata {
+    ModuleMetadata::new(
+        name.to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "tests/metadata.rs".to_string(),
+        "Plugin".to_string(),
+    )
+}
+
+#[test]
+fn metadata_round_trips_through_parts() {
+    let mut original = metadata("parts");
+    original.tags = vec!["fast".to_string()];
+    original.permissions = ModulePermissions::preset("readonly").unwrap();
+    original.deprecated = Some("use other".to_string());
+
+    let rebuilt = ModuleMetadata::from_parts(original.clone().into_parts());
+
+    assert_eq!(rebuilt, original);
+}
+
+#[test]
+fn truncate_name_for_display_keeps_multibyte_characters_whole() {
+    let name = "模块".repeat(20);
+
+    let shown = truncate_name_for_display(&name, 10);
+
+    assert!(shown.chars().count() <= 10);
+    assert!(shown.ends_with(TRUNCATION_MARKER));
+    assert!(name.starts_with(shown.trim_end_matches(TRUNCATION_MARKER)));
+}
+
+#[test]
+fn sandbox_config_round_trips_through_spec() {
+    let configs = [
+        SandboxConfig::default(),
+        SandboxConfig::from_spec("off").unwrap(),
+        SandboxConfig::from_spec("iso:net,proc;allow:/tmp,/var/data").unwrap(),
+    ];
+
+    for config in configs {
+        assert_eq!(SandboxConfig::from_spec(&config.to_spec()).unwrap(), config);
+    }
+}
+
+#[test]
+fn sandbox_spec_rejects_malformed_input() {
+    for spec in ["iso:fs,gpu", "iso:fs,", "readonly", "deny"] {
+        assert!(SandboxConfig::from_spec(spec).is_err(), "{spec}");
+    }
+}
+
+#[test]
+fn annotations_read_back_after_setting() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_metadata(metadata("annotated"), factory)
+        .unwrap();
+
+    registry
+        .set_annotation("annotated", "owner", "audio-team")
+        .unwrap();
+    registry
+        .set_annotation("annotated", "tier", "gold")
+        .unwrap();
+
+    assert_eq!(
+        registry.get_annotation("annotated", "owner").as_deref(),
+        Some("audio-team")
+    );
+    assert_eq!(
+        registry.get_annotation("annotated", "tier").as_deref(),
+        Some("gold")
+    );
+    assert_eq!(registry.get_annotation("annotated", "missing"), None);
+}
+
+struct Resampler;
+
+impl Module for Resampler {
+    fn name(&self) -> &str {
+        "resampler"
+    }
+
+    fn module_type(&self) -> &str {
+        "audio"
+    }
+}
+
+#[test]
+fn metadata_from_module_copies_name_and_type() {
+    let metadata = ModuleMetadata::from_module(&Resampler);
+
+    assert_eq!(metadata.name, "resampler");
+    assert_eq!(metadata.module_type, "audio");
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::Arc;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+#[test]
+fn rename_moves_module_and_keeps_stats() {
+    let registry = ModuleRegistry::new();
+    registry.register("old", "plugin", factory).unwrap();
+    registry.create_any("old").unwrap();
+
+    registry.rename("old", "new").unwrap();
+    assert!(!registry.has_module("old"));
+    assert_eq!(registry.get_metadata("new").unwrap().name, "new");
+    assert_eq!(registry.creation_stats("new").unwrap().creation_count, 1);
+}
+
+#[test]
+fn rename_missing_module_fails() {
+    let registry = ModuleRegistry::new();
+    assert!(registry.rename("missing", "new").is_err());
+}
+
+#[test]
+fn rename_to_existing_name_fails() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "plugin", factory).unwrap();
+    registry.register("b", "plugin", factory).unwrap();
+
+    let error = registry.rename("a", "b").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::AlreadyRegistered(_))
+    ));
+    assert!(registry.has_module("a"));
+}
+
+#[test]
+fn rename_with_alias_resolves_old_name() {
+    let registry = ModuleRegistry::new();
+    registry.register("old", "plugin", factory).unwrap();
+    registry.rename_with_alias("old", "new").unwrap();
+
+    assert!(registry.create_any("old").is_ok());
+    assert!(registry.remove_alias("old"));
+    assert!(!registry.has_module("old"));
+}
+
+#[test]
+fn rename_trims_new_name() {
+    let registry = ModuleRegistry::new();
+    registry.register("a", "plugin", factory).unwrap();
+    registry.rename("a", " b ").unwrap();
+
+    assert!(registry.has_module("b"));
+    assert_eq!(registry.list_modules(), ["b"]);
+}
+
+#[test]
+fn describe_lists_aliases() {
+    let registry = ModuleRegistry::new();
+    registry.register("old", "plugin", factory).unwrap();
+    registry.rename_with_alias("old", "new").unwrap();
+
+    assert_eq!(registry.describe("new").unwrap().aliases, ["old"]);
+    assert_eq!(registry.describe("old").unwrap().metadata.name, "new");
+    assert_eq!(registry.describe_all()[0].aliases, ["old"]);
+}
+
+#[test]
+fn key_normalizer_matches_equivalent_names() {
+    let registry = ModuleRegistry::new();
+    registry.set_key_normalizer(Arc::new(|name: &str| name.to_lowercase().replace('-', "_")));
+
+    registry.register("My-Mod", "plugin", factory).unwrap();
+
+    assert!(registry.create_any("my_mod").is_ok());
+    assert_eq!(registry.get_metadata("my_mod").unwrap().name, "My-Mod");
+}
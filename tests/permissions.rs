@@ -0,0 +1,193 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+/// Registry with one module per built-in preset except `readonly`
+fn preset_registry() -> ModuleRegistry {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_with_preset("plain", "plugin", factory, "none")
+        .unwrap();
+    registry
+        .register_with_preset("client", "plugin", factory, "network-client")
+        .unwrap();
+    registry
+        .register_with_preset("admin", "plugin", factory, "trusted")
+        .unwrap();
+    registry
+}
+
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+}
+
+#[test]
+fn network_client_is_subset_of_trusted() {
+    let client = ModulePermissions::preset("network-client").unwrap();
+    let trusted = ModulePermissions::preset("trusted").unwrap();
+
+    assert!(client.is_subset_of(&trusted));
+}
+
+#[test]
+fn network_access_is_not_subset_of_filesystem_only() {
+    let client = ModulePermissions::preset("network-client").unwrap();
+    let readonly = ModulePermissions::preset("readonly").unwrap();
+
+    assert!(!client.is_subset_of(&readonly));
+}
+
+#[test]
+fn intersect_takes_lower_limits() {
+    let a = ModulePermissions {
+        network_access: true,
+        memory_limit_mb: 256,
+        cpu_limit_percent: 80,
+        timeout_seconds: 10,
+        ..ModulePermissions::default()
+    };
+    let b = ModulePermissions {
+        network_access: true,
+        filesystem_access: true,
+        memory_limit_mb: 128,
+        cpu_limit_percent: 90,
+        timeout_seconds: 30,
+        ..ModulePermissions::default()
+    };
+
+    let both = a.intersect(&b);
+    assert!(both.network_access);
+    assert!(!both.filesystem_access);
+    assert_eq!(both.memory_limit_mb, 128);
+    assert_eq!(both.cpu_limit_percent, 80);
+    assert_eq!(both.timeout_seconds, 10);
+    assert!(both.is_subset_of(&a) && both.is_subset_of(&b));
+
+    let either = a.union(&b);
+    assert!(either.filesystem_access);
+    assert_eq!(either.memory_limit_mb, 256);
+}
+
+#[test]
+fn modules_requiring_matches_mixed_permissions() {
+    let registry = preset_registry();
+
+    assert_eq!(
+        sorted(registry.modules_requiring(Permission::NetworkAccess)),
+        ["admin", "client"]
+    );
+    assert_eq!(
+        registry.modules_requiring(Permission::SystemAccess),
+        ["admin"]
+    );
+}
+
+#[test]
+fn missing_permissions_lists_ungranted_ones() {
+    let registry = preset_registry();
+
+    let missing = registry
+        .missing_permissions(
+            "client",
+            &[
+                Permission::NetworkAccess,
+                Permission::FilesystemAccess,
+                Permission::EnvAccess,
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        missing,
+        [Permission::FilesystemAccess, Permission::EnvAccess]
+    );
+}
+
+#[test]
+fn memory_budget_sums_module_limits() {
+    let registry = ModuleRegistry::new();
+    registry.register("small", "plugin", factory).unwrap();
+    registry
+        .register_metadata(
+            ModuleMetadata::secure(
+                "large".to_string(),
+                "plugin".to_string(),
+                "factory".to_string(),
+                "tests/permissions.rs".to_string(),
+                "Module".to_string(),
+                None,
+                ModulePermissions {
+                    memory_limit_mb: 1024,
+                    ..ModulePermissions::default()
+                },
+                None,
+            ),
+            factory,
+        )
+        .unwrap();
+
+    let total = DEFAULT_MEMORY_LIMIT_MB + 1024;
+    assert_eq!(registry.total_memory_budget_mb(), total);
+    assert!(registry.over_budget(total - 1));
+    assert!(!registry.over_budget(total));
+}
+
+#[test]
+fn register_with_preset_applies_builtin_and_custom_presets() {
+    let registry = preset_registry();
+    let sandboxed = ModulePermissions {
+        network_access: true,
+        memory_limit_mb: 64,
+        ..ModulePermissions::default()
+    };
+    registry
+        .register_preset("sandboxed", sandboxed.clone())
+        .unwrap();
+    registry
+        .register_with_preset("worker", "plugin", factory, "sandboxed")
+        .unwrap();
+
+    assert_eq!(
+        registry.get_metadata("client").unwrap().permissions,
+        ModulePermissions::preset("network-client").unwrap()
+    );
+    assert_eq!(
+        registry.get_metadata("worker").unwrap().permissions,
+        sandboxed
+    );
+
+    let err = registry
+        .register_with_preset("ghost", "plugin", factory, "missing")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::UnknownPreset(preset)) if preset == "missing"
+    ));
+    assert!(!registry.has_module("ghost"));
+}
+
+#[test]
+fn disable_where_locks_down_system_access() {
+    let registry = preset_registry();
+
+    let disabled = registry.disable_where(|metadata| metadata.permissions.system_access);
+
+    assert_eq!(disabled, ["admin"]);
+    assert_eq!(sorted(registry.list_enabled()), ["client", "plain"]);
+    assert!(registry.create_any("admin").is_err());
+    assert!(registry
+        .disable_where(|metadata| metadata.permissions.system_access)
+        .is_empty());
+
+    assert_eq!(registry.enable_where(|_| true), ["admin"]);
+    assert_eq!(
+        sorted(registry.list_enabled()),
+        ["admin", "client", "plain"]
+    );
+}
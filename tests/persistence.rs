@@ -0,0 +1,120 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::fs;
+use std::path::PathBuf;
+
+/// Fresh directory under the system temp dir, unique per test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("module-registry-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+const CORE_MANIFEST: &str = r#"{
+    "name": "core-only",
+    "module_type": "plugin",
+    "instantiate_fn_name": "factory",
+    "module_path": "plugins::core",
+    "struct_name": "CorePlugin",
+    "signature": null,
+    "permissions": {
+        "filesystem_access": false,
+        "network_access": false,
+        "process_spawn": false,
+        "env_access": false,
+        "system_access": false,
+        "memory_limit_mb": 64,
+        "cpu_limit_percent": 50,
+        "timeout_seconds": 10
+    },
+    "review_status": "Pending",
+    "supply_chain": null,
+    "sandbox_config": {
+        "enabled": true,
+        "filesystem_isolation": true,
+        "network_isolation": true,
+        "process_isolation": true,
+        "read_only_fs": true,
+        "allowed_paths": [],
+        "denied_paths": []
+    }
+}"#;
+
+#[test]
+fn load_metadata_dir_accepts_core_fields_only() {
+    let dir = temp_dir("core-manifest");
+    fs::write(dir.join("core.json"), CORE_MANIFEST).unwrap();
+
+    let registry = ModuleRegistry::new();
+    let report = registry.load_metadata_dir(&dir).unwrap();
+    assert_eq!(report.loaded, 1, "{:?}", report.failed);
+
+    let metadata = registry.get_metadata("core-only").unwrap();
+    assert!(metadata.enabled);
+    assert!(metadata.tags.is_empty());
+    assert!(metadata.dependencies.is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn review_audit_detects_a_tampered_middle_line() {
+    let registry = ModuleRegistry::new();
+    let path = temp_dir("audit").join("reviews.jsonl");
+    let statuses = [
+        CodeReviewStatus::InProgress,
+        CodeReviewStatus::Approved {
+            reviewer: "alice".to_string(),
+            timestamp: 1,
+        },
+        CodeReviewStatus::Rejected {
+            reviewer: "bob".to_string(),
+            reason: "unsafe io".to_string(),
+            timestamp: 2,
+        },
+    ];
+    for status in &statuses {
+        registry
+            .append_review_audit(&path, "audited", status)
+            .unwrap();
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    assert!(ModuleRegistry::verify_review_audit(&path).unwrap());
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    lines[1] = lines[1].replace("alice", "mallory");
+    fs::write(&path, lines.join("\n")).unwrap();
+
+    assert!(!ModuleRegistry::verify_review_audit(&path).unwrap());
+}
+
+#[test]
+fn load_metadata_dir_reports_malformed_files() {
+    let dir = temp_dir("mixed-manifests");
+    for name in ["first", "second"] {
+        let metadata = ModuleMetadata::new(
+            name.to_string(),
+            "plugin".to_string(),
+            "factory".to_string(),
+            "plugins".to_string(),
+            "Plugin".to_string(),
+        );
+        fs::write(
+            dir.join(format!("{name}.json")),
+            serde_json::to_string(&metadata).unwrap(),
+        )
+        .unwrap();
+    }
+    fs::write(dir.join("broken.json"), "{ \"name\": ").unwrap();
+
+    let registry = ModuleRegistry::new();
+    let report = registry.load_metadata_dir(&dir).unwrap();
+
+    assert_eq!(report.loaded, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert!(report.failed[0].0.ends_with("broken.json"));
+    assert_eq!(registry.list_modules_sorted(), ["first", "second"]);
+    fs::remove_dir_all(dir).unwrap();
+}
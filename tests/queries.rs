@@ -0,0 +1,255 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn registry_with(modules: &[(&str, &str)]) -> ModuleRegistry {
+    let registry = ModuleRegistry::new();
+    for (name, module_type) in modules {
+        registry.register(name, module_type, factory).unwrap();
+    }
+    registry
+}
+
+#[test]
+fn fingerprint_ignores_registration_order() {
+    let forward = registry_with(&[("a", "alpha"), ("b", "beta"), ("c", "gamma")]);
+    let backward = registry_with(&[("c", "gamma"), ("b", "beta"), ("a", "alpha")]);
+    let different = registry_with(&[("a", "alpha"), ("b", "beta")]);
+
+    assert_eq!(forward.fingerprint(), backward.fingerprint());
+    assert_ne!(forward.fingerprint(), different.fingerprint());
+}
+
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+}
+
+#[test]
+fn unused_modules_lists_never_created() {
+    let registry = registry_with(&[("a", "plugin"), ("b", "plugin"), ("c", "plugin")]);
+    registry.create_any("b").unwrap();
+
+    assert_eq!(sorted(registry.unused_modules()), ["a", "c"]);
+}
+
+#[test]
+fn describe_bundles_stats_after_creations() {
+    let registry = registry_with(&[("described", "plugin"), ("idle", "plugin")]);
+    registry.create_any("described").unwrap();
+    registry.create_any("described").unwrap();
+
+    let description = registry.describe("described").unwrap();
+    assert_eq!(description.metadata.name, "described");
+    assert_eq!(description.creation_count, 2);
+    assert!(description.last_created.is_some());
+    assert!(description.enabled);
+    assert!(description.aliases.is_empty());
+
+    let all = registry.describe_all();
+    assert_eq!(all.len(), 2);
+    let idle = all.iter().find(|d| d.metadata.name == "idle").unwrap();
+    assert_eq!(idle.creation_count, 0);
+    assert_eq!(idle.last_created, None);
+}
+
+#[test]
+fn list_with_types_matches_registrations() {
+    let registry = registry_with(&[("opus", "codec"), ("http", "network")]);
+
+    let mut pairs = registry.list_with_types();
+    pairs.sort();
+
+    assert_eq!(
+        pairs,
+        [
+            ("http".to_string(), "network".to_string()),
+            ("opus".to_string(), "codec".to_string()),
+        ]
+    );
+}
+
+/// Registry with two codecs and a network module in different review states
+fn varied_registry() -> ModuleRegistry {
+    let registry = registry_with(&[("opus", "codec"), ("h264", "codec"), ("http", "network")]);
+    registry
+        .update_review_status(
+            "opus",
+            CodeReviewStatus::Approved {
+                reviewer: "alice".to_string(),
+                timestamp: 0,
+            },
+        )
+        .unwrap();
+    registry
+        .update_review_status("h264", CodeReviewStatus::InProgress)
+        .unwrap();
+    registry.set_enabled("h264", false).unwrap();
+    registry
+}
+
+#[test]
+fn status_snapshot_aggregates_the_registry() {
+    let status = varied_registry().status_snapshot();
+
+    assert_eq!(status.total_modules, 3);
+    assert_eq!(status.by_type["codec"], 2);
+    assert_eq!(status.by_type["network"], 1);
+    assert_eq!(status.approved, 1);
+    assert_eq!(status.in_progress, 1);
+    assert_eq!(status.pending, 1);
+    assert_eq!(status.rejected, 0);
+    assert_eq!(status.insecure, 3);
+    assert_eq!(status.disabled, 1);
+}
+
+#[test]
+fn try_for_each_metadata_stops_at_the_first_error() {
+    let registry = registry_with(&[("plain", "plugin")]);
+    for name in ["root-a", "root-b"] {
+        registry
+            .register_with_preset(name, "plugin", factory, "trusted")
+            .unwrap();
+    }
+
+    let mut privileged_seen = 0;
+    let result = registry.try_for_each_metadata(|metadata| {
+        if metadata.permissions.system_access {
+            privileged_seen += 1;
+            return Err(metadata.name.clone());
+        }
+        Ok(())
+    });
+
+    assert!(matches!(result.unwrap_err().as_str(), "root-a" | "root-b"));
+    assert_eq!(privileged_seen, 1);
+}
+
+#[test]
+fn list_modules_sorted_is_deterministic() {
+    let names = ["delta", "alpha", "charlie", "bravo", "echo"];
+    let forward = registry_with(&names.map(|name| (name, "plugin")));
+    let mut reversed = names;
+    reversed.reverse();
+    let backward = registry_with(&reversed.map(|name| (name, "plugin")));
+
+    let sorted = forward.list_modules_sorted();
+    assert_eq!(sorted, ["alpha", "bravo", "charlie", "delta", "echo"]);
+    for _ in 0..5 {
+        assert_eq!(backward.list_modules_sorted(), sorted);
+    }
+}
+
+#[test]
+fn struct_name_histogram_counts_shared_structs() {
+    let registry = ModuleRegistry::new();
+    for (name, struct_name) in [("opus", "Codec"), ("h264", "Codec"), ("http", "Client")] {
+        registry
+            .register_with_metadata(name, "plugin", "factory", "tests", struct_name, factory)
+            .unwrap();
+    }
+
+    let histogram = registry.struct_name_histogram();
+
+    assert_eq!(histogram.len(), 2);
+    assert_eq!(histogram["Codec"], 2);
+    assert_eq!(histogram["Client"], 1);
+}
+
+#[test]
+fn type_tree_nests_dotted_types() {
+    let registry = registry_with(&[
+        ("opus", "codec.audio.opus"),
+        ("h264", "codec.video.h264"),
+        ("raw", "codec"),
+    ]);
+
+    let tree = registry.type_tree();
+    let codec = &tree.children["codec"];
+
+    assert_eq!(
+        codec.children.keys().collect::<Vec<_>>(),
+        ["audio", "video"]
+    );
+    assert_eq!(codec.modules, ["raw"]);
+    assert_eq!(tree.get("codec.audio.opus").unwrap().modules, ["opus"]);
+    assert_eq!(tree.get("codec.video.h264").unwrap().modules, ["h264"]);
+    assert!(tree.get("codec.image").is_none());
+}
+
+#[test]
+fn status_display_lists_total_and_types() {
+    let rendered = varied_registry().status_snapshot().to_string();
+    let rows: Vec<Vec<&str>> = rendered
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+
+    assert!(rows.contains(&vec!["Modules:", "3"]), "{rendered}");
+    assert!(rows.contains(&vec!["Types:", "2"]), "{rendered}");
+    assert_eq!(
+        &rows[rows.len() - 2..],
+        [vec!["codec", "2"], vec!["network", "1"]]
+    );
+
+    let width = rendered.lines().next().unwrap().len();
+    assert!(
+        rendered.lines().all(|line| line.len() == width),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn to_table_renders_header_and_sorted_rows() {
+    let registry = registry_with(&[("http", "network"), ("a", "plugin")]);
+    let table = registry.to_table();
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 4, "{table}");
+    assert_eq!(lines[0], "Name | Type    | Struct | Approved | Signed");
+    assert_eq!(lines[1], "-----+---------+--------+----------+-------");
+    assert_eq!(lines[2], "a    | plugin  | Module | no       | no");
+    assert_eq!(lines[3], "http | network | Module | no       | no");
+}
+
+#[test]
+fn list_sorted_by_type_breaks_ties_by_name() {
+    let registry = registry_with(&[("zlib", "codec"), ("http", "network"), ("aac", "codec")]);
+
+    assert_eq!(
+        registry.list_sorted_by(SortKey::Type),
+        ["aac", "zlib", "http"]
+    );
+}
+
+#[test]
+fn list_sorted_by_name_ignores_registration_order() {
+    let registry = registry_with(&[("zlib", "codec"), ("http", "network"), ("aac", "codec")]);
+
+    assert_eq!(
+        registry.list_sorted_by(SortKey::Name),
+        ["aac", "http", "zlib"]
+    );
+    assert_eq!(
+        registry.list_sorted_by(SortKey::RegistrationIndex),
+        ["zlib", "http", "aac"]
+    );
+}
+
+#[test]
+fn changed_since_tracks_the_last_fingerprint() {
+    let registry = registry_with(&[("a", "alpha")]);
+    let before = registry.fingerprint();
+    assert!(!registry.changed_since(before));
+
+    registry.register("b", "beta", factory).unwrap();
+    assert!(registry.changed_since(before));
+
+    let after = registry.fingerprint();
+    assert!(!registry.changed_since(after));
+}
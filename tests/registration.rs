@@ -0,0 +1,422 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::Arc;
+use std::thread;
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn metadata(name: &str, module_type: &str) -> ModuleMetadata {
+    ModuleMetadata::new(
+        name.to_string(),
+        module_type.to_string(),
+        "factory".to_string(),
+        "registration".to_string(),
+        "Module".to_string(),
+    )
+}
+
+#[test]
+fn validate_registration_accepts_valid_module() {
+    let registry = ModuleRegistry::new();
+    assert!(registry
+        .validate_registration("a", "plugin", &metadata("a", "plugin"))
+        .is_ok());
+    assert!(!registry.has_module("a"));
+}
+
+#[test]
+fn validate_registration_rejects_long_name() {
+    let registry = ModuleRegistry::new();
+    let name = "n".repeat(MAX_MODULE_NAME_LENGTH + 1);
+    assert!(matches!(
+        registry.validate_registration(&name, "plugin", &metadata(&name, "plugin")),
+        Err(RegistryError::NameTooLong { .. })
+    ));
+}
+
+#[test]
+fn validate_registration_rejects_disallowed_type() {
+    let registry = ModuleRegistry::new();
+    registry.set_allowed_types(["plugin"]);
+    assert!(matches!(
+        registry.validate_registration("a", "driver", &metadata("a", "driver")),
+        Err(RegistryError::TypeNotAllowed(_))
+    ));
+}
+
+#[test]
+fn validate_registration_applies_decorator() {
+    let policy = SecurityPolicy {
+        require_signature: false,
+        require_supply_chain: false,
+        ..SecurityPolicy::default()
+    };
+    let registry = ModuleRegistry::with_verify_on_register(policy);
+    registry.set_metadata_decorator(Arc::new(|metadata: &mut ModuleMetadata| {
+        metadata.review_status = CodeReviewStatus::Approved {
+            reviewer: "decorator".to_string(),
+            timestamp: 0,
+        };
+    }));
+
+    let meta = metadata("a", "plugin");
+    assert!(registry.validate_registration("a", "plugin", &meta).is_ok());
+    assert!(registry.register_metadata(meta, factory).is_ok());
+}
+
+#[test]
+fn long_paths_follow_one_policy_across_entry_points() {
+    let long = || {
+        let mut meta = metadata("a", "plugin");
+        meta.module_path = "p".repeat(MAX_PATH_LENGTH + 1);
+        meta
+    };
+
+    let strict = ModuleRegistry::new();
+    assert!(matches!(
+        strict.validate_registration("a", "plugin", &long()),
+        Err(RegistryError::PathTooLong { .. })
+    ));
+    assert!(strict.register_metadata(long(), factory).is_err());
+    assert!(strict.replace_all(vec![(long(), factory)]).is_err());
+
+    let truncating = ModuleRegistry::new();
+    truncating.set_truncate_paths(true);
+    assert!(truncating
+        .validate_registration("a", "plugin", &long())
+        .is_ok());
+    truncating.register_metadata(long(), factory).unwrap();
+    let registered = truncating.get_metadata("a").unwrap().module_path;
+    truncating.replace_all(vec![(long(), factory)]).unwrap();
+    let replaced = truncating.get_metadata("a").unwrap().module_path;
+
+    assert!(registered.len() <= MAX_PATH_LENGTH);
+    assert!(registered.ends_with(TRUNCATION_MARKER));
+    assert_eq!(registered, replaced);
+}
+
+#[test]
+fn get_or_register_inserts_once_across_threads() {
+    let registry = ModuleRegistry::new();
+
+    let inserted = thread::scope(|scope| {
+        let handles: Vec<_> = (0..16)
+            .map(|_| scope.spawn(|| registry.get_or_register("shared", "plugin", factory)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .filter(|&inserted| inserted)
+            .count()
+    });
+
+    assert_eq!(inserted, 1);
+    assert_eq!(registry.count(), 1);
+}
+
+struct ThresholdConfig;
+
+impl ConfigSchema for ThresholdConfig {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": { "threshold": { "type": "number" } }
+        })
+    }
+}
+
+#[test]
+fn register_with_schema_exposes_the_schema() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_with_schema("filter", "plugin", factory, ThresholdConfig::schema)
+        .unwrap();
+
+    let schema = registry.config_schema("filter").unwrap();
+    assert_eq!(schema["properties"]["threshold"]["type"], "number");
+    assert!(registry.config_schema("missing").is_none());
+}
+
+fn is_invalid_name(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidName(_))
+    )
+}
+
+#[test]
+fn register_rejects_empty_name() {
+    let registry = ModuleRegistry::new();
+    let error = registry.register("", "plugin", factory).unwrap_err();
+    assert!(is_invalid_name(&error));
+}
+
+#[test]
+fn register_rejects_whitespace_name() {
+    let registry = ModuleRegistry::new();
+    let error = registry.register("   ", "plugin", factory).unwrap_err();
+    assert!(is_invalid_name(&error));
+    assert_eq!(registry.count(), 0);
+}
+
+#[test]
+fn register_rejects_embedded_newline() {
+    let registry = ModuleRegistry::new();
+    let error = registry
+        .register("bad\nname", "plugin", factory)
+        .unwrap_err();
+    assert!(is_invalid_name(&error));
+}
+
+#[test]
+fn metadata_decorator_tags_registered_modules() {
+    let registry = ModuleRegistry::new();
+    registry.set_metadata_decorator(Arc::new(|metadata: &mut ModuleMetadata| {
+        metadata.tags.push("audited".to_string());
+    }));
+
+    registry.register("tagged", "plugin", factory).unwrap();
+
+    assert!(registry.get_metadata("tagged").unwrap().has_tag("audited"));
+}
+
+fn register_with_path(registry: &ModuleRegistry, path: &str) -> anyhow::Result<()> {
+    registry.register_with_metadata("pathed", "plugin", "factory", path, "Module", factory)
+}
+
+#[test]
+fn module_path_at_the_limit_is_kept() {
+    let registry = ModuleRegistry::new();
+    let path = "p".repeat(MAX_PATH_LENGTH);
+
+    register_with_path(&registry, &path).unwrap();
+
+    assert_eq!(registry.get_metadata("pathed").unwrap().module_path, path);
+}
+
+#[test]
+fn module_path_over_the_limit_is_rejected() {
+    let registry = ModuleRegistry::new();
+    let error = register_with_path(&registry, &"p".repeat(MAX_PATH_LENGTH + 1)).unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::PathTooLong { .. })
+    ));
+}
+
+#[test]
+fn module_path_over_the_limit_is_truncated_when_enabled() {
+    let registry = ModuleRegistry::new();
+    registry.set_truncate_paths(true);
+
+    register_with_path(&registry, &"é".repeat(MAX_PATH_LENGTH)).unwrap();
+
+    let path = registry.get_metadata("pathed").unwrap().module_path;
+    assert!(path.len() <= MAX_PATH_LENGTH);
+    assert!(path.starts_with('é'));
+}
+
+#[test]
+fn strict_type_charset_accepts_valid_types() {
+    let registry = ModuleRegistry::new();
+    registry.set_strict_type_charset(true);
+
+    assert!(registry.register("a", "audio.codec-v2", factory).is_ok());
+}
+
+#[test]
+fn strict_type_charset_rejects_spaces() {
+    let registry = ModuleRegistry::new();
+    registry.set_strict_type_charset(true);
+
+    let error = registry.register("a", "audio codec", factory).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidType(_))
+    ));
+}
+
+#[test]
+fn default_type_charset_accepts_spaces() {
+    let registry = ModuleRegistry::new();
+
+    assert!(registry.register("a", "audio codec", factory).is_ok());
+}
+
+#[test]
+fn register_metadata_stores_builder_output_unchanged() {
+    let registry = ModuleRegistry::new();
+    let meta = ModuleMetadata::from_parts(ModuleMetadataParts {
+        tags: vec!["built".to_string()],
+        permissions: ModulePermissions::preset("network-client").unwrap(),
+        ..metadata("built", "plugin").into_parts()
+    });
+
+    registry.register_metadata(meta.clone(), factory).unwrap();
+
+    assert_eq!(registry.get_metadata("built").unwrap(), meta);
+}
+
+#[test]
+fn type_sandbox_defaults_apply_per_type() {
+    let registry = ModuleRegistry::new();
+    let codec = SandboxConfig::from_spec("iso:fs;ro").unwrap();
+    let network = SandboxConfig::from_spec("iso:proc;allow:/tmp").unwrap();
+    registry.set_type_sandbox_default("codec", codec.clone());
+    registry.set_type_sandbox_default("network", network.clone());
+
+    registry.register("opus", "codec", factory).unwrap();
+    registry.register("http", "network", factory).unwrap();
+    registry.register("other", "plugin", factory).unwrap();
+
+    assert_eq!(registry.get_metadata("opus").unwrap().sandbox_config, codec);
+    assert_eq!(
+        registry.get_metadata("http").unwrap().sandbox_config,
+        network
+    );
+    assert_eq!(
+        registry.get_metadata("other").unwrap().sandbox_config,
+        SandboxConfig::default()
+    );
+}
+
+#[test]
+fn builder_configures_case_insensitivity_and_allowed_types() {
+    let registry = ModuleRegistry::builder()
+        .case_insensitive()
+        .allowed_types(["codec"])
+        .build();
+
+    registry.register("Opus", "codec", factory).unwrap();
+    assert!(registry.create_any("OPUS").is_ok());
+
+    let error = registry.register("http", "network", factory).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::TypeNotAllowed(_))
+    ));
+}
+
+#[test]
+fn registry_handle_upgrades_only_while_shared() {
+    let plain = ModuleRegistry::new();
+    assert!(plain.registry_handle().upgrade().is_none());
+
+    let shared = ModuleRegistry::new().into_shared();
+    shared.register("sibling", "plugin", factory).unwrap();
+    let handle = shared.registry_handle();
+
+    assert!(handle.upgrade().unwrap().has_module("sibling"));
+    drop(shared);
+    assert!(handle.upgrade().is_none());
+}
+
+#[test]
+fn named_globals_do_not_share_modules() {
+    let audio = ModuleRegistry::named_global("test-audio");
+    let video = ModuleRegistry::named_global("test-video");
+    audio.register("opus", "codec", factory).unwrap();
+    video.register("h264", "codec", factory).unwrap();
+
+    assert!(std::ptr::eq(
+        audio,
+        ModuleRegistry::named_global("test-audio")
+    ));
+    assert!(!std::ptr::eq(audio, video));
+    assert!(audio.has_module("opus") && !audio.has_module("h264"));
+    assert!(video.has_module("h264") && !video.has_module("opus"));
+    assert!(!ModuleRegistry::global().has_module("opus"));
+}
+
+#[test]
+fn reserved_module_is_listed_but_not_ready() {
+    let registry = ModuleRegistry::new();
+    registry.reserve("late", "plugin").unwrap();
+
+    assert!(registry.has_module("late"));
+    let err = registry.create_any("late").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::NotReady(name)) if name == "late"
+    ));
+}
+
+#[test]
+fn fulfilled_module_can_be_created() {
+    let registry = ModuleRegistry::new();
+    registry.reserve("late", "plugin").unwrap();
+    registry.fulfill("late", factory).unwrap();
+
+    assert_eq!(*registry.create::<u32>("late").unwrap(), 0);
+    let err = registry.fulfill("late", factory).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::AlreadyRegistered(_))
+    ));
+}
+
+fn context_factory(_: &RegistryContext<'_>) -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn schema() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// Register `name` as a `driver` through every register path, one result per path
+fn register_every_way(registry: &ModuleRegistry, name: &str) -> Vec<anyhow::Result<()>> {
+    vec![
+        registry.register(name, "driver", factory),
+        registry.register_metadata(metadata(name, "driver"), factory),
+        registry.register_secure(
+            name,
+            "driver",
+            factory,
+            None,
+            ModulePermissions::default(),
+            None,
+        ),
+        registry.register_singleton(name, "driver", factory),
+        registry.register_typed::<u32>(name, "driver", factory),
+        registry.register_with_schema(name, "driver", factory, schema),
+        registry.register_with_precondition(name, "driver", factory, || Ok(())),
+        registry.register_with_context(name, "driver", context_factory),
+        registry.register_with_preset(name, "driver", factory, "none"),
+        registry.reserve(name, "driver"),
+        registry.get_or_register(name, "driver", factory).map(drop),
+    ]
+}
+
+#[test]
+fn every_register_path_enforces_the_type_allowlist() {
+    let registry = ModuleRegistry::new();
+    registry.set_allowed_types(["plugin"]);
+
+    for result in register_every_way(&registry, "blocked") {
+        let err = result.unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<RegistryError>(),
+                Some(RegistryError::TypeNotAllowed(_))
+            ),
+            "{err}"
+        );
+    }
+    assert!(!registry.has_module("blocked"));
+}
+
+#[test]
+fn every_register_path_trims_the_name() {
+    let registry = ModuleRegistry::new();
+
+    for result in register_every_way(&registry, "  padded  ") {
+        result.unwrap();
+        assert_eq!(registry.list_modules(), ["padded"]);
+        assert_eq!(registry.get_metadata("padded").unwrap().name, "padded");
+    }
+}
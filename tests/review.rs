@@ -0,0 +1,203 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn metadata(name: &str) -> ModuleMetadata {
+    ModuleMetadata::new(
+        name.to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "tests/review.rs".to_string(),
+        "Plugin".to_string(),
+    )
+}
+
+fn approved(reviewer: &str) -> CodeReviewStatus {
+    CodeReviewStatus::Approved {
+        reviewer: reviewer.to_string(),
+        timestamp: 1_700_000_000,
+    }
+}
+
+#[test]
+fn review_update_shows_in_modified_since() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_metadata(
+            ModuleMetadata {
+                last_modified: 0,
+                ..metadata("reviewed")
+            },
+            factory,
+        )
+        .unwrap();
+    let since = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert!(registry.modified_since(since).is_empty());
+
+    registry
+        .update_review_status("reviewed", approved("alice"))
+        .unwrap();
+    assert_eq!(registry.modified_since(since), ["reviewed"]);
+}
+
+#[test]
+fn review_accessors_cover_every_status() {
+    let rejected = CodeReviewStatus::Rejected {
+        reviewer: "bob".to_string(),
+        reason: "unsafe io".to_string(),
+        timestamp: 1_700_000_100,
+    };
+
+    for status in [CodeReviewStatus::Pending, CodeReviewStatus::InProgress] {
+        assert_eq!(status.reviewer(), None);
+        assert_eq!(status.timestamp(), None);
+        assert_eq!(status.rejection_reason(), None);
+    }
+
+    let status = approved("alice");
+    assert_eq!(status.reviewer(), Some("alice"));
+    assert_eq!(status.timestamp(), Some(1_700_000_000));
+    assert_eq!(status.rejection_reason(), None);
+
+    assert_eq!(rejected.reviewer(), Some("bob"));
+    assert_eq!(rejected.timestamp(), Some(1_700_000_100));
+    assert_eq!(rejected.rejection_reason(), Some("unsafe io"));
+}
+
+#[test]
+fn review_status_buckets_hold_one_of_each() {
+    let registry = ModuleRegistry::new();
+    let statuses = [
+        ("pending", CodeReviewStatus::Pending),
+        ("in-progress", CodeReviewStatus::InProgress),
+        ("approved", approved("alice")),
+        (
+            "rejected",
+            CodeReviewStatus::Rejected {
+                reviewer: "bob".to_string(),
+                reason: "unsafe io".to_string(),
+                timestamp: 0,
+            },
+        ),
+    ];
+    for (name, status) in &statuses {
+        registry.register(name, "plugin", factory).unwrap();
+        registry.update_review_status(name, status.clone()).unwrap();
+    }
+
+    let counts = registry.review_status_counts();
+    assert_eq!(counts.len(), 4);
+    assert!(counts.values().all(|&count| count == 1));
+    for (name, status) in &statuses {
+        assert_eq!(registry.list_by_review_status(status), [*name]);
+        assert_eq!(counts[status.as_str()], 1);
+    }
+}
+
+fn signature(code_hash: &str) -> ModuleSignature {
+    ModuleSignature {
+        code_hash: code_hash.to_string(),
+        signature: "sig".to_string(),
+        public_key: "key".to_string(),
+        timestamp: 0,
+        algorithm: DEFAULT_SIGNATURE_ALGORITHM.to_string(),
+    }
+}
+
+#[test]
+fn changed_code_hash_makes_approval_stale() {
+    let registry = ModuleRegistry::new();
+    let mut signed = metadata("signed");
+    signed.signature = Some(signature("v1"));
+    registry.register_metadata(signed, factory).unwrap();
+    registry
+        .update_review_status("signed", approved("alice"))
+        .unwrap();
+
+    assert!(registry.stale_approvals().is_empty());
+
+    registry
+        .update_signature("signed", Some(signature("v2")))
+        .unwrap();
+    assert_eq!(registry.stale_approvals(), ["signed"]);
+
+    registry
+        .update_review_status("signed", approved("bob"))
+        .unwrap();
+    assert!(registry.stale_approvals().is_empty());
+}
+
+#[test]
+fn compare_and_set_review_rejects_a_stale_expectation() {
+    let registry = ModuleRegistry::new();
+    registry.register("shared", "plugin", factory).unwrap();
+    let pending = CodeReviewStatus::Pending;
+
+    assert!(registry
+        .compare_and_set_review("shared", &pending, approved("alice"))
+        .unwrap());
+
+    // Bob still believes the module is pending
+    let rejected = CodeReviewStatus::Rejected {
+        reviewer: "bob".to_string(),
+        reason: "unsafe".to_string(),
+        timestamp: 1_700_000_000,
+    };
+    assert!(!registry
+        .compare_and_set_review("shared", &pending, rejected)
+        .unwrap());
+    assert_eq!(
+        registry.get_metadata("shared").unwrap().review_status,
+        approved("alice")
+    );
+
+    assert!(registry
+        .compare_and_set_review("missing", &pending, approved("alice"))
+        .is_err());
+}
+
+struct SteppedClock(Mutex<u64>);
+
+impl SteppedClock {
+    fn set(&self, secs: u64) {
+        *self.0.lock().unwrap() = secs;
+    }
+}
+
+impl Clock for SteppedClock {
+    fn now(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(*self.0.lock().unwrap())
+    }
+}
+
+#[test]
+fn modification_times_follow_the_registry_clock() {
+    let clock = Arc::new(SteppedClock(Mutex::new(1_000)));
+    let registry = ModuleRegistry::builder().clock(clock.clone()).build();
+    registry.register("early", "plugin", factory).unwrap();
+    registry.register("late", "plugin", factory).unwrap();
+
+    clock.set(2_000);
+    registry
+        .update_review_status("late", approved("alice"))
+        .unwrap();
+    registry.create_any("early").unwrap();
+
+    assert_eq!(registry.get_metadata("early").unwrap().last_modified, 1_000);
+    assert_eq!(registry.modified_since(1_001), ["late"]);
+    assert_eq!(registry.modified_since(1_000).len(), 2);
+    assert_eq!(
+        registry.creation_stats("early").unwrap().last_created,
+        Some(2_000)
+    );
+}
@@ -0,0 +1,460 @@
+#![cfg(feature = "full")]
+
+use module_registry::*;
+use std::any::Any;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn factory() -> anyhow::Result<Box<dyn Any + Send + Sync>> {
+    Ok(Box::new(0u32))
+}
+
+fn trusted_metadata(name: &str) -> ModuleMetadata {
+    let mut metadata = ModuleMetadata::new(
+        name.to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "security".to_string(),
+        "Module".to_string(),
+    );
+    metadata.permissions = ModulePermissions::preset("trusted").unwrap();
+    metadata.review_status = CodeReviewStatus::Approved {
+        reviewer: "mallory".to_string(),
+        timestamp: 0,
+    };
+    metadata
+}
+
+/// Metadata signed over `code_hash` and built from a commit expected to produce `expected_hash`
+fn provenance_metadata(code_hash: &str, expected_hash: &str) -> ModuleMetadata {
+    let mut metadata = trusted_metadata("built");
+    metadata.signature = Some(ModuleSignature {
+        code_hash: code_hash.to_string(),
+        signature: "sig".to_string(),
+        public_key: "key".to_string(),
+        timestamp: 0,
+        algorithm: "ed25519".to_string(),
+    });
+    metadata.supply_chain = Some(SupplyChainInfo {
+        source_url: "https://example.com/repo".to_string(),
+        commit_hash: "abc123".to_string(),
+        build_timestamp: 0,
+        dependencies: Default::default(),
+        build_environment: "ci".to_string(),
+        verifier_signature: None,
+        expected_code_hash: Some(expected_hash.to_string()),
+    });
+    metadata
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Pending-review metadata with a current signature and verified supply chain
+fn signed_metadata(name: &str) -> ModuleMetadata {
+    let mut metadata = ModuleMetadata::new(
+        name.to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "security".to_string(),
+        "Module".to_string(),
+    );
+    metadata.signature = Some(ModuleSignature {
+        code_hash: "hash".to_string(),
+        signature: "sig".to_string(),
+        public_key: "key".to_string(),
+        timestamp: now(),
+        algorithm: DEFAULT_SIGNATURE_ALGORITHM.to_string(),
+    });
+    metadata.supply_chain = Some(SupplyChainInfo {
+        source_url: "https://example.com/repo".to_string(),
+        commit_hash: "abc123".to_string(),
+        build_timestamp: 0,
+        dependencies: Default::default(),
+        build_environment: "ci".to_string(),
+        verifier_signature: None,
+        expected_code_hash: None,
+    });
+    metadata
+}
+
+fn is_sealed_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::SecuritySealed(_))
+    )
+}
+
+#[test]
+fn seal_blocks_updates_but_not_new_modules() {
+    let registry = ModuleRegistry::new();
+    registry.register("existing", "plugin", factory).unwrap();
+    registry.seal_security();
+
+    let error = registry
+        .update_permissions("existing", ModulePermissions::preset("trusted").unwrap())
+        .unwrap_err();
+    assert!(is_sealed_error(&error));
+    assert!(registry.register("fresh", "plugin", factory).is_ok());
+}
+
+#[test]
+fn seal_blocks_replacing_registrations() {
+    let registry = ModuleRegistry::new();
+    registry.register("existing", "plugin", factory).unwrap();
+    registry.seal_security();
+
+    let error = registry
+        .register_metadata(trusted_metadata("existing"), factory)
+        .unwrap_err();
+    assert!(is_sealed_error(&error));
+    let error = registry
+        .register_with_preset("existing", "plugin", factory, "trusted")
+        .unwrap_err();
+    assert!(is_sealed_error(&error));
+    let error = registry
+        .register("existing", "plugin", factory)
+        .unwrap_err();
+    assert!(is_sealed_error(&error));
+    let error = registry
+        .replace_all(vec![(trusted_metadata("existing"), factory)])
+        .unwrap_err();
+    assert!(is_sealed_error(&error));
+
+    let metadata = registry.get_metadata("existing").unwrap();
+    assert!(!metadata.permissions.system_access);
+    assert_eq!(metadata.review_status, CodeReviewStatus::Pending);
+}
+
+#[test]
+fn matching_provenance_verifies() {
+    let metadata = provenance_metadata("hash-a", "hash-a");
+
+    assert!(SecurityValidator::verify_provenance(&metadata).unwrap());
+    let check = SecurityValidator::comprehensive_check(&metadata);
+    assert!(!check.issues.iter().any(|i| i.component == "provenance"));
+}
+
+#[test]
+fn mismatched_provenance_is_reported() {
+    let metadata = provenance_metadata("hash-a", "hash-b");
+
+    assert!(!SecurityValidator::verify_provenance(&metadata).unwrap());
+    let check = SecurityValidator::comprehensive_check(&metadata);
+    assert!(check.issues.iter().any(|i| i.component == "provenance"));
+}
+
+#[test]
+fn verify_on_register_rejects_unsigned_system_access() {
+    let registry = ModuleRegistry::with_verify_on_register(SecurityPolicy::default());
+    let mut metadata = trusted_metadata("rootkit");
+    metadata.permissions.system_access = true;
+
+    let error = registry.register_metadata(metadata, factory).unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::SecurityCheckFailed { .. })
+    ));
+    assert!(!registry.has_module("rootkit"));
+}
+
+#[test]
+fn modules_by_risk_sorts_highest_first() {
+    let registry = ModuleRegistry::new();
+    let mut secure = signed_metadata("secure");
+    secure.review_status = CodeReviewStatus::Approved {
+        reviewer: "alice".to_string(),
+        timestamp: 0,
+    };
+    registry.register_metadata(secure, factory).unwrap();
+    registry
+        .register_metadata(signed_metadata("unreviewed"), factory)
+        .unwrap();
+    registry.register("unsigned", "plugin", factory).unwrap();
+
+    assert_eq!(
+        registry.modules_by_risk(),
+        [
+            ("unsigned".to_string(), SecurityRiskLevel::High),
+            ("unreviewed".to_string(), SecurityRiskLevel::Medium),
+            ("secure".to_string(), SecurityRiskLevel::None),
+        ]
+    );
+}
+
+#[test]
+fn non_preferred_algorithm_is_reported_as_downgrade() {
+    let policy = SecurityPolicy {
+        allowed_algorithms: vec![
+            "Ed25519".to_string(),
+            DEFAULT_SIGNATURE_ALGORITHM.to_string(),
+        ],
+        preferred_algorithm: Some("Ed25519".to_string()),
+        ..SecurityPolicy::default()
+    };
+    let metadata = signed_metadata("legacy-signed");
+
+    let check = SecurityValidator::comprehensive_check_with_policy(&metadata, &policy);
+
+    let downgrade = check
+        .issues
+        .iter()
+        .find(|issue| issue.component == "signature_algorithm")
+        .expect("downgrade issue");
+    assert!(matches!(downgrade.severity, SecuritySeverity::Medium));
+    assert!(!check
+        .issues
+        .iter()
+        .any(|issue| issue.component == "signature"));
+}
+
+struct FixedClock(SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+#[test]
+fn signatures_expiring_within_uses_the_registry_clock() {
+    let now = 2_000_000_000;
+    let registry = ModuleRegistry::builder()
+        .clock(Arc::new(FixedClock(UNIX_EPOCH + Duration::from_secs(now))))
+        .build();
+    for (name, signed_at) in [
+        ("expiring", now - SIGNATURE_EXPIRY_SECONDS + 100),
+        ("fresh", now),
+    ] {
+        let mut metadata = signed_metadata(name);
+        metadata.signature.as_mut().unwrap().timestamp = signed_at;
+        registry.register_metadata(metadata, factory).unwrap();
+    }
+
+    assert_eq!(
+        registry.signatures_expiring_within(1_000),
+        [("expiring".to_string(), 100)]
+    );
+}
+
+#[test]
+fn verify_all_signatures_reports_each_module() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_metadata(signed_metadata("valid"), factory)
+        .unwrap();
+    let mut expired = signed_metadata("expired");
+    expired.signature.as_mut().unwrap().timestamp = now() - SIGNATURE_EXPIRY_SECONDS - 100;
+    registry.register_metadata(expired, factory).unwrap();
+    registry.register("unsigned", "plugin", factory).unwrap();
+
+    let results = registry.verify_all_signatures();
+
+    assert_eq!(results.len(), 3);
+    assert!(results["valid"]);
+    assert!(!results["expired"]);
+    assert!(!results["unsigned"]);
+}
+
+/// Metadata with a sandbox that blocks nothing, so each test adds one contradiction
+fn open_sandbox_metadata() -> ModuleMetadata {
+    let mut metadata = ModuleMetadata::new(
+        "sandboxed".to_string(),
+        "plugin".to_string(),
+        "factory".to_string(),
+        "security".to_string(),
+        "Module".to_string(),
+    );
+    metadata.sandbox_config = SandboxConfig {
+        enabled: true,
+        filesystem_isolation: false,
+        network_isolation: false,
+        process_isolation: false,
+        read_only_fs: false,
+        allowed_paths: Vec::new(),
+        denied_paths: Vec::new(),
+    };
+    metadata
+}
+
+fn consistency_messages(metadata: &ModuleMetadata) -> Vec<String> {
+    metadata
+        .validate_consistency()
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect()
+}
+
+#[test]
+fn open_sandbox_is_consistent() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions = ModulePermissions::preset("trusted").unwrap();
+
+    assert!(metadata.validate_consistency().is_empty());
+}
+
+#[test]
+fn network_access_contradicts_network_isolation() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions.network_access = true;
+    metadata.sandbox_config.network_isolation = true;
+
+    let messages = consistency_messages(&metadata);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("Network"), "{messages:?}");
+}
+
+#[test]
+fn process_spawn_contradicts_process_isolation() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions.process_spawn = true;
+    metadata.sandbox_config.process_isolation = true;
+
+    let messages = consistency_messages(&metadata);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("Process"), "{messages:?}");
+}
+
+#[test]
+fn filesystem_access_contradicts_isolation_without_allowed_paths() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions.filesystem_access = true;
+    metadata.sandbox_config.filesystem_isolation = true;
+
+    let messages = consistency_messages(&metadata);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("isolated"), "{messages:?}");
+
+    metadata.sandbox_config.allowed_paths = vec!["/data".to_string()];
+    assert!(metadata.validate_consistency().is_empty());
+}
+
+#[test]
+fn filesystem_access_contradicts_read_only_without_allowed_paths() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions.filesystem_access = true;
+    metadata.sandbox_config.read_only_fs = true;
+
+    let messages = consistency_messages(&metadata);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("read-only"), "{messages:?}");
+}
+
+#[test]
+fn path_both_allowed_and_denied_is_reported() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.sandbox_config.allowed_paths = vec!["/data".to_string(), "/tmp".to_string()];
+    metadata.sandbox_config.denied_paths = vec!["/tmp".to_string()];
+
+    assert_eq!(
+        consistency_messages(&metadata),
+        ["Sandbox path is both allowed and denied: /tmp"]
+    );
+}
+
+#[test]
+fn disabled_sandbox_blocks_nothing() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions = ModulePermissions::preset("trusted").unwrap();
+    metadata.sandbox_config = SandboxConfig {
+        enabled: false,
+        ..SandboxConfig::default()
+    };
+
+    assert!(metadata.validate_consistency().is_empty());
+}
+
+#[test]
+fn comprehensive_check_surfaces_contradictions() {
+    let mut metadata = open_sandbox_metadata();
+    metadata.permissions.network_access = true;
+    metadata.sandbox_config.network_isolation = true;
+
+    let check = SecurityValidator::comprehensive_check(&metadata);
+    assert!(check
+        .warnings
+        .iter()
+        .any(|warning| warning.component == "consistency"));
+}
+
+/// Split one CSV record, unquoting fields and undoubling inner quotes
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+#[test]
+fn security_report_csv_round_trips() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_metadata(signed_metadata("signed"), factory)
+        .unwrap();
+    registry
+        .register("codec, \"fast\"", "plugin", factory)
+        .unwrap();
+
+    let csv = registry.security_report_csv();
+    let rows: Vec<Vec<String>> = csv.lines().map(parse_csv_line).collect();
+
+    assert_eq!(rows.len(), 3, "{csv}");
+    assert_eq!(rows[0][0], "module");
+    assert_eq!(rows[0][6], "risk_level");
+    assert!(rows.iter().all(|row| row.len() == 7), "{csv}");
+    assert_eq!(
+        rows[1],
+        [
+            "codec, \"fast\"",
+            "false",
+            "false",
+            "false",
+            "false",
+            "true",
+            "High"
+        ]
+    );
+    assert_eq!(rows[2][0], "signed");
+    assert_eq!(rows[2][2], "true");
+    assert_eq!(rows[2][6], "Medium");
+}
+
+#[test]
+fn security_report_csv_rows_are_consistent_under_concurrent_renames() {
+    let registry = ModuleRegistry::new();
+    registry.register("churn-a", "plugin", factory).unwrap();
+    let done = std::sync::atomic::AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..2000 {
+                registry.rename("churn-a", "churn-b").unwrap();
+                registry.rename("churn-b", "churn-a").unwrap();
+            }
+            done.store(true, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        while !done.load(std::sync::atomic::Ordering::SeqCst) {
+            let csv = registry.security_report_csv();
+            for row in csv.lines().skip(1).map(parse_csv_line) {
+                assert_eq!(row[6], "High", "{csv}");
+            }
+        }
+    });
+}
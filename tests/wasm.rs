@@ -0,0 +1,74 @@
+#![cfg(feature = "wasm")]
+
+use module_registry::*;
+
+/// Hand-assembled module exporting `answer: () -> i32` that returns 42
+const ANSWER_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f, // type section: () -> i32
+    0x03, 0x02, 0x01, 0x00, // function section: one function of type 0
+    0x07, 0x0a, 0x01, 0x06, b'a', b'n', b's', b'w', b'e', b'r', 0x00, 0x00, // export "answer"
+    0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x2a, 0x0b, // code section: i32.const 42
+];
+
+#[test]
+fn wasm_module_registers_and_creates() {
+    let registry = ModuleRegistry::new();
+    registry
+        .register_wasm("answer", "plugin", ANSWER_WASM)
+        .unwrap();
+
+    assert_eq!(
+        registry.get_metadata("answer").unwrap().struct_name,
+        "WasmInstance"
+    );
+    let instance = registry.create::<WasmInstance>("answer").unwrap();
+    assert_eq!(instance.exports(), ["answer"]);
+    let results = instance.call("answer", &[]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].i32(), Some(42));
+    assert!(instance.call("missing", &[]).is_err());
+}
+
+/// Hand-assembled module exporting `answer: () -> i32` and `spin: () -> i32`, which never returns
+const SPIN_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f, // type section: () -> i32
+    0x03, 0x03, 0x02, 0x00, 0x00, // function section: two functions of type 0
+    0x07, 0x11, 0x02, // export section: two exports
+    0x06, b'a', b'n', b's', b'w', b'e', b'r', 0x00, 0x00, // "answer" -> function 0
+    0x04, b's', b'p', b'i', b'n', 0x00, 0x01, // "spin" -> function 1
+    0x0a, 0x10, 0x02, // code section: two bodies
+    0x04, 0x00, 0x41, 0x2a, 0x0b, // i32.const 42
+    0x09, 0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x41, 0x00, 0x0b, // loop br 0 end; i32.const 0
+];
+
+#[test]
+fn looping_export_traps_when_its_fuel_runs_out() {
+    let registry = ModuleRegistry::new();
+    registry.register_wasm("spin", "plugin", SPIN_WASM).unwrap();
+    let permissions = ModulePermissions {
+        cpu_limit_percent: 10,
+        timeout_seconds: 1,
+        ..ModulePermissions::default()
+    };
+    registry.update_permissions("spin", permissions).unwrap();
+
+    let instance = registry.create::<WasmInstance>("spin").unwrap();
+    for _ in 0..2 {
+        let err = instance.call("spin", &[]).unwrap_err();
+        assert!(format!("{err:#}").contains("fuel"), "{err:#}");
+    }
+    let results = instance.call("answer", &[]).unwrap();
+    assert_eq!(results[0].i32(), Some(42));
+}
+
+#[test]
+fn invalid_wasm_is_rejected_at_registration() {
+    let registry = ModuleRegistry::new();
+
+    assert!(registry
+        .register_wasm("broken", "plugin", b"not wasm")
+        .is_err());
+    assert!(!registry.has_module("broken"));
+}